@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use osm2lanes::locale::{DrivingSide, Locale};
 use osm2lanes::tag::{Tags, TagsWrite};
-use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig};
+use osm2lanes::transform::{tags_to_lanes, tags_to_lanes_json, TagsToLanesConfig};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
@@ -43,3 +43,18 @@ pub fn js_tags_to_lanes(val: &JsValue) -> JsValue {
     let lanes = tags_to_lanes(&tags, &locale, &config).unwrap();
     JsValue::from_serde(&lanes).unwrap()
 }
+
+/// Tags as a JSON object to the road, warnings and their codes as JSON
+#[wasm_bindgen]
+pub fn js_tags_to_lanes_json(tags_json: &str, drive_on_right: bool) -> String {
+    utils::set_panic_hook();
+
+    let locale = Locale::builder()
+        .driving_side(if drive_on_right {
+            DrivingSide::Right
+        } else {
+            DrivingSide::Left
+        })
+        .build();
+    tags_to_lanes_json(tags_json, &locale, &TagsToLanesConfig::default())
+}
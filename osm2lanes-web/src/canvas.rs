@@ -61,9 +61,12 @@ impl Component for Canvas {
     }
 
     fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        let state = ctx.props().state.borrow();
         if let Err(e) = self.draw_canvas(
-            ctx.props().state.borrow().road.as_ref(),
-            &ctx.props().state.borrow().locale,
+            state.road.as_ref(),
+            &state.locale,
+            &state.draw_options,
+            state.draw_split,
         ) {
             ctx.props().callback_error.emit(format!("Error: {}", e));
         }
@@ -71,7 +74,13 @@ impl Component for Canvas {
 }
 
 impl Canvas {
-    fn draw_canvas(&self, road: Option<&Road>, locale: &Locale) -> Result<(), RenderError> {
+    fn draw_canvas(
+        &self,
+        road: Option<&Road>,
+        locale: &Locale,
+        options: &draw::Options,
+        split: bool,
+    ) -> Result<(), RenderError> {
         if let Some(road) = road {
             let window = window().unwrap();
             let canvas = window
@@ -96,7 +105,12 @@ impl Canvas {
             context.scale(dpr, dpr).unwrap();
             let mut rc = WebRenderContext::new(context, window);
 
-            draw::lanes(&mut rc, (canvas_width, canvas_height), road, locale)?;
+            let size = (canvas_width, canvas_height);
+            if split {
+                draw::lanes_split(&mut rc, size, road, locale, options)?;
+            } else {
+                draw::lanes(&mut rc, size, road, locale, options)?;
+            }
         }
         Ok(())
     }
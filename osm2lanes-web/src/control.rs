@@ -59,6 +59,10 @@ impl Component for Control {
 
         let driving_side_onchange = ctx.link().callback(|_e: Event| Msg::ToggleDrivingSide);
 
+        let ruler_onchange = ctx.link().callback(|_e: Event| Msg::ToggleRuler);
+        let numbers_onchange = ctx.link().callback(|_e: Event| Msg::ToggleNumbers);
+        let split_onchange = ctx.link().callback(|_e: Event| Msg::ToggleSplit);
+
         let country_onchange = ctx.link().callback(|e: Event| {
             let selected: String = e.target_unchecked_into::<HtmlSelectElement>().value();
             let selected = Country::from_alpha2(selected);
@@ -124,6 +128,31 @@ impl Component for Control {
                         }
                     }
                     <hr/>
+                    <label class="row-item">
+                        <input
+                            type="checkbox"
+                            checked={state.draw_options.ruler}
+                            onchange={ruler_onchange}
+                        />
+                        {"Ruler"}
+                    </label>
+                    <label class="row-item">
+                        <input
+                            type="checkbox"
+                            checked={state.draw_options.numbers}
+                            onchange={numbers_onchange}
+                        />
+                        {"Lane numbers"}
+                    </label>
+                    <label class="row-item">
+                        <input
+                            type="checkbox"
+                            checked={state.draw_split}
+                            onchange={split_onchange}
+                        />
+                        {"Split by direction"}
+                    </label>
+                    <hr/>
                     <label class="row-item" for="way">{"OSM Way ID"}</label>
                     <input class="row-item" type="text" id="way" name="way" size="12"
                         ref={state.way_ref.clone()}
@@ -1,7 +1,10 @@
 use osm2lanes::locale::Locale;
 use osm2lanes::metric::Metre;
-use osm2lanes::road::{Color as MarkingColor, Direction, Lane, Printable, Road, Style};
-use piet::kurbo::{Line, Point, Rect};
+use osm2lanes::road::{
+    Color as MarkingColor, Direction, Lane, ParkingOrientation, Printable, Road, Style,
+    TurnDirection,
+};
+use piet::kurbo::{Affine, Line, Point, Rect, Vec2};
 use piet::{
     Color as PietColor, FontFamily, RenderContext, StrokeStyle, Text, TextAttribute,
     TextLayoutBuilder,
@@ -19,12 +22,77 @@ fn color_into(c: MarkingColor) -> PietColor {
     }
 }
 
+/// Fill for lanes on a road with a non-asphalt `surface`, if any
+fn surface_color(surface: Option<&str>) -> Option<PietColor> {
+    match surface? {
+        "gravel" | "fine_gravel" | "compacted" | "pebblestone" => {
+            Some(PietColor::rgb8(0xb4, 0x9a, 0x70))
+        },
+        "unpaved" | "dirt" | "earth" | "ground" | "mud" => Some(PietColor::rgb8(0x7a, 0x5a, 0x3a)),
+        "sett" | "cobblestone" | "unhewn_cobblestone" | "paving_stones" => {
+            Some(PietColor::rgb8(0x70, 0x70, 0x78))
+        },
+        "grass" => Some(PietColor::rgb8(0x5a, 0x8a, 0x3c)),
+        _ => None,
+    }
+}
+
 struct Scale(f64);
 
 impl Scale {
     fn scale(&self, m: Metre) -> f64 {
         self.0 * m.val()
     }
+
+    /// Lines between the bays of a parking lane spanning `left` to `right` on the canvas,
+    /// angled for diagonal parking.
+    fn parking_bays(
+        &self,
+        orientation: ParkingOrientation,
+        (left, right): (f64, f64),
+        height: f64,
+    ) -> Vec<Line> {
+        let (bay_length, slant) = match orientation {
+            ParkingOrientation::Parallel => (Metre::new(6.0), 0.0),
+            ParkingOrientation::Diagonal => (Metre::new(3.0), right - left),
+            ParkingOrientation::Perpendicular => (Metre::new(2.5), 0.0),
+        };
+        let spacing = self.scale(bay_length);
+        (0..)
+            .map(|i| f64::from(i) * spacing)
+            .take_while(|y| *y <= height)
+            .map(|y| Line::new((left, y), (right, y + slant)))
+            .collect()
+    }
+
+    /// The two wheel ruts of a track lane spanning `left` to `right` on the canvas,
+    /// about a car's track apart, with the grass between them left out.
+    fn wheel_tracks(&self, (left, right): (f64, f64), height: f64) -> [Rect; 2] {
+        let centre = 0.5 * (left + right);
+        let inner = self.scale(Metre::new(0.6));
+        let outer = self.scale(Metre::new(1.0));
+        [
+            Rect::new((centre - outer).max(left), 0.0, centre - inner, height),
+            Rect::new(centre + inner, 0.0, (centre + outer).min(right), height),
+        ]
+    }
+
+    /// Canvas x position and distance of a tick for every whole metre,
+    /// starting at `start` and covering `length`.
+    fn ticks(&self, start: Metre, length: Metre) -> impl Iterator<Item = (f64, u32)> + '_ {
+        (0..)
+            .take_while(move |&i| f64::from(i) <= length.val())
+            .map(move |i| (self.scale(start + Metre::new(f64::from(i))), i))
+    }
+}
+
+/// Optional overlays drawn over the lanes, all off by default
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Options {
+    /// A metric ruler above the road
+    pub ruler: bool,
+    /// The number of each travel lane within its direction
+    pub numbers: bool,
 }
 
 pub fn lanes<R: RenderContext>(
@@ -32,6 +100,7 @@ pub fn lanes<R: RenderContext>(
     (canvas_width, canvas_height): (u32, u32),
     road: &Road,
     locale: &Locale,
+    options: &Options,
 ) -> Result<(), RenderError> {
     let canvas_width = canvas_width as f64;
     let canvas_height = canvas_height as f64;
@@ -44,8 +113,11 @@ pub fn lanes<R: RenderContext>(
         canvas_width / (road.width(locale) + 2.0 * grassy_verge + 2.0 * asphalt_buffer).val(),
     );
 
-    // Background
-    rc.clear(None, PietColor::OLIVE);
+    // Background, filled rather than cleared to respect any transform
+    rc.fill(
+        Rect::new(0.0, 0.0, canvas_width, canvas_height),
+        &PietColor::OLIVE,
+    );
 
     rc.fill(
         Rect::new(
@@ -57,18 +129,55 @@ pub fn lanes<R: RenderContext>(
         &PietColor::BLACK,
     );
 
+    let surface = surface_color(road.surface.as_deref());
+    // Anything less firm than a paved `tracktype=grade1` is drawn as two wheel ruts
+    let wheel_tracks = road.track_grade.map_or(false, |grade| grade >= 2);
     let mut left_edge = grassy_verge + asphalt_buffer;
+    let numbers = if options.numbers {
+        lane_numbers(road)
+    } else {
+        vec![None; road.lanes.len()]
+    };
 
-    for lane in &road.lanes {
+    for (lane, number) in road.lanes.iter().zip(numbers) {
         match lane {
             Lane::Travel {
-                direction,
-                designated,
-                width,
-                ..
+                direction, turn, ..
             } => {
-                let width =
-                    width.unwrap_or_else(|| locale.travel_width(designated, road.highway.r#type()));
+                let width = lane.width(locale, road.highway.r#type());
+                if wheel_tracks && !lane.is_foot() {
+                    rc.fill(
+                        Rect::new(
+                            scale.scale(left_edge),
+                            0.0,
+                            scale.scale(left_edge + width),
+                            canvas_height,
+                        ),
+                        &PietColor::rgb8(0x5a, 0x8a, 0x3c),
+                    );
+                    let ruts = scale.wheel_tracks(
+                        (scale.scale(left_edge), scale.scale(left_edge + width)),
+                        canvas_height,
+                    );
+                    for rut in ruts {
+                        rc.fill(
+                            rut,
+                            surface
+                                .as_ref()
+                                .unwrap_or(&PietColor::rgb8(0x7a, 0x5a, 0x3a)),
+                        );
+                    }
+                } else if let (Some(color), false) = (&surface, lane.is_foot()) {
+                    rc.fill(
+                        Rect::new(
+                            scale.scale(left_edge),
+                            0.0,
+                            scale.scale(left_edge + width),
+                            canvas_height,
+                        ),
+                        color,
+                    );
+                }
                 let x = scale.scale(left_edge + (0.5 * width));
                 if let Some(direction) = direction {
                     draw_arrow(
@@ -79,14 +188,18 @@ pub fn lanes<R: RenderContext>(
                         },
                         *direction,
                     )?;
-                    draw_arrow(
-                        rc,
-                        Point {
-                            x,
-                            y: 0.7 * canvas_height,
+                    let mid = Point {
+                        x,
+                        y: 0.7 * canvas_height,
+                    };
+                    match turn {
+                        Some(turn) if *direction != Direction::Both => {
+                            for turn in turn {
+                                draw_turn(rc, mid, *turn, *direction)?;
+                            }
                         },
-                        *direction,
-                    )?;
+                        _ => draw_arrow(rc, mid, *direction)?,
+                    }
                 }
                 if lane.is_foot() {
                     rc.fill(
@@ -107,6 +220,49 @@ pub fn lanes<R: RenderContext>(
                     .default_attribute(TextAttribute::TextColor(PietColor::WHITE))
                     .build()?;
                 rc.draw_text(&layout, (x - (0.5 * font_size), 0.5 * canvas_height));
+                if let Some(number) = number {
+                    let font_size = 16.0;
+                    let layout = rc
+                        .text()
+                        .new_text_layout(number.to_string())
+                        .font(FontFamily::MONOSPACE, font_size)
+                        .default_attribute(TextAttribute::TextColor(PietColor::YELLOW))
+                        .build()?;
+                    rc.draw_text(&layout, (x - (0.25 * font_size), 0.1 * canvas_height));
+                }
+                left_edge += width;
+            },
+            Lane::Parking {
+                designated,
+                width,
+                orientation: Some(orientation),
+                ..
+            } => {
+                let width =
+                    width.unwrap_or_else(|| locale.travel_width(designated, road.highway.r#type()));
+                if let Some(color) = &surface {
+                    rc.fill(
+                        Rect::new(
+                            scale.scale(left_edge),
+                            0.0,
+                            scale.scale(left_edge + width),
+                            canvas_height,
+                        ),
+                        color,
+                    );
+                }
+                let bays = scale.parking_bays(
+                    *orientation,
+                    (scale.scale(left_edge), scale.scale(left_edge + width)),
+                    canvas_height,
+                );
+                for bay in bays {
+                    rc.stroke(
+                        bay,
+                        &PietColor::WHITE,
+                        scale.scale(locale.separator_widths.line),
+                    );
+                }
                 left_edge += width;
             },
             Lane::Parking {
@@ -114,6 +270,17 @@ pub fn lanes<R: RenderContext>(
             } => {
                 let width =
                     width.unwrap_or_else(|| locale.travel_width(designated, road.highway.r#type()));
+                if let Some(color) = &surface {
+                    rc.fill(
+                        Rect::new(
+                            scale.scale(left_edge),
+                            0.0,
+                            scale.scale(left_edge + width),
+                            canvas_height,
+                        ),
+                        color,
+                    );
+                }
                 let x = scale.scale(left_edge + (0.5 * width));
                 let font_size = 24.0;
                 let layout = rc
@@ -125,7 +292,7 @@ pub fn lanes<R: RenderContext>(
                 rc.draw_text(&layout, (x - (0.5 * font_size), 0.5 * canvas_height));
                 left_edge += width;
             },
-            Lane::Shoulder { width } => {
+            Lane::Shoulder { width, .. } => {
                 let width = width.unwrap_or(default_lane_width);
                 let x = scale.scale(left_edge + (0.5 * width));
                 let font_size = 24.0;
@@ -140,11 +307,11 @@ pub fn lanes<R: RenderContext>(
             },
             Lane::Separator { markings } => {
                 for marking in markings.iter() {
-                    let width = marking.width.unwrap_or_else(|| Metre::new(0.2));
+                    let width = marking.width.unwrap_or(locale.separator_widths.line);
                     let x = scale.scale(left_edge + 0.5 * width);
                     let color = match (marking.style, marking.color) {
                         (_, Some(c)) => color_into(c),
-                        (Style::KerbUp | Style::KerbDown, None) => PietColor::GRAY,
+                        (Style::KerbUp | Style::KerbDown | Style::Median, None) => PietColor::GRAY,
                         // Remains for debugging
                         _ => PietColor::BLUE,
                         // _ => return Err(RenderError::UnknownSeparator),
@@ -164,7 +331,9 @@ pub fn lanes<R: RenderContext>(
                             Style::DottedLine => StrokeStyle::new().dash_pattern(&[50.0, 100.0]),
                             Style::DashedLine => StrokeStyle::new().dash_pattern(&[100.0, 100.0]),
                             Style::BrokenLine => StrokeStyle::new().dash_pattern(&[100.0, 50.0]),
-                            Style::KerbUp | Style::KerbDown => StrokeStyle::new(),
+                            Style::KerbUp | Style::KerbDown | Style::Median => StrokeStyle::new(),
+                            // Coloured paint filling a buffer
+                            Style::NoFill if marking.color.is_some() => StrokeStyle::new(),
                             // Remains for debugging, SOS
                             _ => StrokeStyle::new().dash_pattern(&[
                                 10.0, 10.0, 10.0, 10.0, 10.0, 50.0, 30.0, 30.0, 30.0, 30.0, 30.0,
@@ -179,10 +348,126 @@ pub fn lanes<R: RenderContext>(
         }
     }
 
+    if options.ruler {
+        draw_ruler(
+            rc,
+            &scale,
+            grassy_verge + asphalt_buffer,
+            road.width(locale),
+        )?;
+    }
+
     rc.finish().unwrap();
     Ok(())
 }
 
+/// Number of each travel lane within its direction, from 1, for debugging.
+/// Lanes are counted from the left as seen travelling in their direction,
+/// so backward lanes are numbered right to left.
+fn lane_numbers(road: &Road) -> Vec<Option<u32>> {
+    let mut numbers = vec![None; road.lanes.len()];
+    for wanted in [Direction::Forward, Direction::Backward, Direction::Both] {
+        let mut indices: Vec<usize> = road
+            .lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, lane)| match lane {
+                Lane::Travel {
+                    direction: Some(direction),
+                    ..
+                } if *direction == wanted => Some(index),
+                _ => None,
+            })
+            .collect();
+        if wanted == Direction::Backward {
+            indices.reverse();
+        }
+        for (index, number) in indices.into_iter().zip(1..) {
+            if let Some(slot) = numbers.get_mut(index) {
+                *slot = Some(number);
+            }
+        }
+    }
+    numbers
+}
+
+/// Forward lanes and backward lanes as separate roads, left to right.
+/// Lanes in both directions are in both, lanes without a direction in neither.
+fn split_by_direction(road: &Road) -> (Road, Road) {
+    let with_direction = |wanted: Direction| {
+        let mut road = road.clone();
+        road.retain_lanes(|lane| {
+            let direction = match lane {
+                Lane::Travel { direction, .. } => *direction,
+                Lane::Parking { direction, .. } => Some(*direction),
+                Lane::Shoulder { .. } | Lane::Separator { .. } => None,
+            };
+            direction == Some(wanted) || direction == Some(Direction::Both)
+        });
+        road
+    };
+    (
+        with_direction(Direction::Forward),
+        with_direction(Direction::Backward),
+    )
+}
+
+/// Diagnostic view, forward lanes drawn above backward lanes
+pub fn lanes_split<R: RenderContext>(
+    rc: &mut R,
+    (canvas_width, canvas_height): (u32, u32),
+    road: &Road,
+    locale: &Locale,
+    options: &Options,
+) -> Result<(), RenderError> {
+    let (forward, backward) = split_by_direction(road);
+    let half_height = canvas_height / 2;
+    lanes(rc, (canvas_width, half_height), &forward, locale, options)?;
+    rc.save()?;
+    rc.transform(Affine::translate((0.0, f64::from(half_height))));
+    let result = lanes(rc, (canvas_width, half_height), &backward, locale, options);
+    rc.restore()?;
+    result
+}
+
+fn draw_ruler<R: RenderContext>(
+    rc: &mut R,
+    scale: &Scale,
+    start: Metre,
+    length: Metre,
+) -> Result<(), RenderError> {
+    let y = 10.0;
+    rc.stroke(
+        Line::new(
+            Point {
+                x: scale.scale(start),
+                y,
+            },
+            Point {
+                x: scale.scale(start + length),
+                y,
+            },
+        ),
+        &PietColor::WHITE,
+        1.0,
+    );
+    for (x, distance) in scale.ticks(start, length) {
+        rc.stroke(
+            Line::new(Point { x, y }, Point { x, y: y + 10.0 }),
+            &PietColor::WHITE,
+            1.0,
+        );
+        let layout = rc
+            .text()
+            .new_text_layout(format!("{}m", distance))
+            .font(FontFamily::SYSTEM_UI, 12.0)
+            .default_attribute(TextAttribute::TextColor(PietColor::WHITE))
+            .build()?;
+        rc.draw_text(&layout, (x + 2.0, y + 2.0));
+    }
+    Ok(())
+}
+
 pub fn draw_arrow<R: RenderContext>(
     rc: &mut R,
     mid: Point,
@@ -240,3 +525,321 @@ pub fn draw_arrow<R: RenderContext>(
     }
     Ok(())
 }
+
+/// Path of a turn arrow around its middle, from the tail to the head, turning to the left.
+/// Turns to the right are mirrored.
+fn turn_path(turn: TurnDirection) -> Vec<Vec2> {
+    let path: &[(f64, f64)] = match turn {
+        TurnDirection::None => &[],
+        TurnDirection::Through => &[(0.0, 20.0), (0.0, -20.0)],
+        TurnDirection::Left | TurnDirection::Right => &[(0.0, 20.0), (0.0, 0.0), (-20.0, 0.0)],
+        TurnDirection::SlightLeft | TurnDirection::SlightRight => {
+            &[(0.0, 20.0), (0.0, 0.0), (-14.0, -14.0)]
+        },
+        TurnDirection::SharpLeft | TurnDirection::SharpRight => {
+            &[(0.0, 20.0), (0.0, -10.0), (-14.0, 4.0)]
+        },
+        TurnDirection::Reverse => &[(0.0, 20.0), (0.0, -10.0), (-12.0, -10.0), (-12.0, 10.0)],
+        TurnDirection::MergeToLeft | TurnDirection::MergeToRight => {
+            &[(0.0, 20.0), (0.0, 5.0), (-12.0, -20.0)]
+        },
+    };
+    let mirror = matches!(
+        turn,
+        TurnDirection::Right
+            | TurnDirection::SlightRight
+            | TurnDirection::SharpRight
+            | TurnDirection::MergeToRight
+    );
+    path.iter()
+        .map(|&(x, y)| Vec2::new(if mirror { -x } else { x }, y))
+        .collect()
+}
+
+/// Turn arrow, such as one of the markings of `turn:lanes=*`,
+/// turned around for lanes travelling backward
+pub fn draw_turn<R: RenderContext>(
+    rc: &mut R,
+    mid: Point,
+    turn: TurnDirection,
+    direction: Direction,
+) -> Result<(), RenderError> {
+    let mut path = turn_path(turn);
+    if direction == Direction::Backward {
+        for point in &mut path {
+            *point = -*point;
+        }
+    }
+    for segment in path.windows(2) {
+        if let [from, to] = segment {
+            rc.stroke(Line::new(mid + *from, mid + *to), &PietColor::WHITE, 1.0);
+        }
+    }
+    if let [.., from, to] = path.as_slice() {
+        let back = (*from - *to).normalize() * 10.0;
+        for angle in [0.5, -0.5] {
+            let wing = (Affine::rotate(angle) * back.to_point()).to_vec2();
+            rc.stroke(
+                Line::new(mid + *to, mid + *to + wing),
+                &PietColor::WHITE,
+                1.0,
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use osm2lanes::locale::{DrivingSide, Locale};
+    use osm2lanes::metric::Metre;
+    use osm2lanes::road::{ParkingOrientation, TurnDirection};
+    use osm2lanes::tag::Tags;
+    use osm2lanes::transform::{tags_to_lanes, TagsToLanesConfig};
+    use piet::kurbo::Size;
+
+    use super::{lane_numbers, lanes, lanes_split, split_by_direction, turn_path, Options, Scale};
+
+    #[test]
+    fn ruler_ticks_follow_scale() {
+        let scale = Scale(100.0);
+        let ticks: Vec<(f64, u32)> = scale.ticks(Metre::new(1.0), Metre::new(3.5)).collect();
+        assert_eq!(ticks, vec![(100.0, 0), (200.0, 1), (300.0, 2), (400.0, 3)]);
+    }
+
+    #[test]
+    fn ruler_svg() {
+        let tags = Tags::from_str_pairs(&[["highway", "residential"], ["lanes", "2"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let svg = |ruler: bool| {
+            let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 480.0));
+            lanes(
+                &mut rc,
+                (640, 480),
+                &road,
+                &locale,
+                &Options {
+                    ruler,
+                    ..Options::default()
+                },
+            )
+            .unwrap();
+            let mut svg = Vec::new();
+            rc.write(&mut svg).unwrap();
+            String::from_utf8(svg).unwrap()
+        };
+        let with_ruler = svg(true);
+        let without_ruler = svg(false);
+
+        // The same scale and offset as `lanes`, a 1m verge and a 0.1m buffer either side
+        let (grassy_verge, asphalt_buffer) = (Metre::new(1.0), Metre::new(0.1));
+        let scale =
+            Scale(640.0 / (road.width(&locale) + 2.0 * grassy_verge + 2.0 * asphalt_buffer).val());
+        let ticks: Vec<f64> = scale
+            .ticks(grassy_verge + asphalt_buffer, road.width(&locale))
+            .map(|(x, _)| x)
+            .collect();
+        assert!(!ticks.is_empty());
+        for x in ticks {
+            let tick = format!("M{},10", x);
+            assert!(with_ruler.contains(&tick), "missing tick at {}", x);
+            assert!(with_ruler.contains(&format!("L{},20", x)));
+            assert!(!without_ruler.contains(&tick));
+        }
+    }
+
+    #[test]
+    fn split_svg() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "3"],
+            ["lanes:forward", "2"],
+            ["lanes:backward", "1"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let write = |rc: piet_svg::RenderContext| {
+            let mut svg = Vec::new();
+            rc.write(&mut svg).unwrap();
+            String::from_utf8(svg).unwrap()
+        };
+        // Path data, unaffected by the transform to the lower half
+        let paths = |svg: &str| -> Vec<String> {
+            svg.split(" d=\"")
+                .skip(1)
+                .filter_map(|path| path.split('"').next())
+                .map(ToOwned::to_owned)
+                .collect()
+        };
+
+        let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 480.0));
+        lanes_split(&mut rc, (640, 480), &road, &locale, &Options::default()).unwrap();
+        let split = paths(&write(rc));
+
+        let (forward, backward) = split_by_direction(&road);
+        assert_eq!(forward.lanes.len(), 2);
+        assert_eq!(backward.lanes.len(), 1);
+        for half in [forward, backward] {
+            let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 240.0));
+            lanes(&mut rc, (640, 240), &half, &locale, &Options::default()).unwrap();
+            for path in paths(&write(rc)) {
+                assert!(split.contains(&path), "missing path {}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn lane_numbers_svg() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "3"],
+            ["lanes:forward", "2"],
+            ["lanes:backward", "1"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        assert_eq!(lane_numbers(&road), vec![Some(1), Some(1), Some(2)]);
+
+        let svg = |numbers: bool| {
+            let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 480.0));
+            let options = Options {
+                numbers,
+                ..Options::default()
+            };
+            lanes(&mut rc, (640, 480), &road, &locale, &options).unwrap();
+            let mut svg = Vec::new();
+            rc.write(&mut svg).unwrap();
+            String::from_utf8(svg).unwrap()
+        };
+        assert_ne!(svg(true), svg(false));
+    }
+
+    #[test]
+    fn parking_bays_by_orientation() {
+        let scale = Scale(10.0);
+        let bays = |orientation| scale.parking_bays(orientation, (0.0, 20.0), 100.0);
+        // Bays every 6m, 3m and 2.5m over 10m
+        assert_eq!(bays(ParkingOrientation::Parallel).len(), 2);
+        assert_eq!(bays(ParkingOrientation::Diagonal).len(), 4);
+        assert_eq!(bays(ParkingOrientation::Perpendicular).len(), 5);
+        assert!(bays(ParkingOrientation::Diagonal)
+            .iter()
+            .all(|line| line.p1.y > line.p0.y));
+    }
+
+    #[test]
+    fn parking_orientation_svg() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let svg = |orientation: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["parking:lane:right", orientation],
+            ])
+            .unwrap();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 480.0));
+            lanes(&mut rc, (640, 480), &road, &locale, &Options::default()).unwrap();
+            let mut svg = Vec::new();
+            rc.write(&mut svg).unwrap();
+            String::from_utf8(svg).unwrap()
+        };
+        let parallel = svg("parallel");
+        let diagonal = svg("diagonal");
+        let perpendicular = svg("perpendicular");
+        assert_ne!(parallel, diagonal);
+        assert_ne!(parallel, perpendicular);
+        assert_ne!(diagonal, perpendicular);
+    }
+
+    #[test]
+    fn surface_svg() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let svg = |surface: &str| {
+            let tags = Tags::from_str_pairs(&[["highway", "track"], ["surface", surface]]).unwrap();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 480.0));
+            lanes(&mut rc, (640, 480), &road, &locale, &Options::default()).unwrap();
+            let mut svg = Vec::new();
+            rc.write(&mut svg).unwrap();
+            String::from_utf8(svg).unwrap()
+        };
+        assert_ne!(svg("gravel"), svg("asphalt"));
+    }
+
+    #[test]
+    fn wheel_tracks_within_lane() {
+        let scale = Scale(10.0);
+        let [left, right] = scale.wheel_tracks((0.0, 35.0), 100.0);
+        assert_eq!((left.x0, left.x1), (7.5, 11.5));
+        assert_eq!((right.x0, right.x1), (23.5, 27.5));
+        // Ruts are clipped to a lane narrower than the car's track
+        let [left, right] = scale.wheel_tracks((0.0, 10.0), 100.0);
+        assert_eq!((left.x0, right.x1), (0.0, 10.0));
+    }
+
+    #[test]
+    fn track_grade_svg() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let svg = |tracktype: &str| {
+            let tags =
+                Tags::from_str_pairs(&[["highway", "track"], ["tracktype", tracktype]]).unwrap();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            assert!(road.track_grade.is_some());
+            let mut rc = piet_svg::RenderContext::new(Size::new(640.0, 480.0));
+            lanes(&mut rc, (640, 480), &road, &locale, &Options::default()).unwrap();
+            let mut svg = Vec::new();
+            rc.write(&mut svg).unwrap();
+            String::from_utf8(svg).unwrap()
+        };
+        assert_ne!(svg("grade3"), svg("grade1"));
+    }
+
+    #[test]
+    fn turn_paths() {
+        for turn in [
+            TurnDirection::Through,
+            TurnDirection::Left,
+            TurnDirection::SlightLeft,
+            TurnDirection::SharpLeft,
+            TurnDirection::Right,
+            TurnDirection::SlightRight,
+            TurnDirection::SharpRight,
+            TurnDirection::Reverse,
+            TurnDirection::MergeToLeft,
+            TurnDirection::MergeToRight,
+        ] {
+            let path = turn_path(turn);
+            assert!(path.len() >= 2, "{:?}", turn);
+            let mirrored: Vec<_> = turn_path(turn.mirror())
+                .into_iter()
+                .map(|point| (-point.x, point.y))
+                .collect();
+            if !matches!(turn, TurnDirection::Through | TurnDirection::Reverse) {
+                assert_eq!(
+                    mirrored,
+                    path.iter()
+                        .map(|point| (point.x, point.y))
+                        .collect::<Vec<_>>(),
+                    "{:?}",
+                    turn
+                );
+            }
+        }
+        assert!(turn_path(TurnDirection::None).is_empty());
+    }
+}
@@ -39,6 +39,10 @@ pub struct State {
     pub normalized_tags: Option<String>,
     /// Lanes to visualise
     pub road: Option<Road>,
+    /// Overlays to draw over the lanes
+    pub draw_options: draw::Options,
+    /// Draw the forward lanes above the backward lanes
+    pub draw_split: bool,
     /// Message for user
     pub message: Option<String>,
     /// Ref to input for way id
@@ -54,6 +58,9 @@ pub enum Msg {
         locale: Locale,
     },
     ToggleDrivingSide,
+    ToggleRuler,
+    ToggleNumbers,
+    ToggleSplit,
     CountrySet(Result<Country, &'static str>),
     WayFetch,
     Error(String),
@@ -76,6 +83,8 @@ impl Component for App {
             edit_tags,
             normalized_tags: None,
             road: None,
+            draw_options: draw::Options::default(),
+            draw_split: false,
             message: None,
             way_ref: NodeRef::default(),
         }));
@@ -113,6 +122,21 @@ impl Component for App {
                 self.update_tags();
                 true
             },
+            Msg::ToggleRuler => {
+                let mut state = self.state.borrow_mut();
+                state.draw_options.ruler = !state.draw_options.ruler;
+                true
+            },
+            Msg::ToggleNumbers => {
+                let mut state = self.state.borrow_mut();
+                state.draw_options.numbers = !state.draw_options.numbers;
+                true
+            },
+            Msg::ToggleSplit => {
+                let mut state = self.state.borrow_mut();
+                state.draw_split = !state.draw_split;
+                true
+            },
             Msg::CountrySet(Ok(country)) => {
                 {
                     let mut state = self.state.borrow_mut();
@@ -221,7 +245,7 @@ impl App {
         log::trace!("Locale: {:?}", locale);
         match Tags::from_str(value) {
             Ok(tags) => match tags_to_lanes(&tags, locale, &TagsToLanesConfig::default()) {
-                Ok(RoadFromTags { road, warnings }) => {
+                Ok(RoadFromTags { road, warnings, .. }) => {
                     match lanes_to_tags(&road, locale, &LanesToTagsConfig::new(false)) {
                         Ok(tags) => {
                             state.road = Some(road);
@@ -2,17 +2,41 @@ pub use celes::Country;
 use serde::{Deserialize, Serialize};
 
 use crate::metric::Metre;
-use crate::road::{Color, Designated};
-use crate::tag::{HighwayImportance, HighwayType};
+use crate::road::{Color, Cycleway, Designated, Marking};
+use crate::tag::{HighwayImportance, HighwayType, NonTravel};
 
 /// Context about the place where an OSM way exists.
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Locale {
     /// The ISO 3166 Country
     pub country: Option<Country>,
     pub iso_3166_2_subdivision: Option<String>,
     /// The driving side
     pub driving_side: DrivingSide,
+    /// Default widths of separators
+    #[serde(default)]
+    pub separator_widths: SeparatorWidths,
+}
+
+/// Default widths of separator markings, where not otherwise specified.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeparatorWidths {
+    /// Painted line
+    pub line: Metre,
+    /// Space between painted lines
+    pub space: Metre,
+    /// Kerb
+    pub kerb: Metre,
+}
+
+impl Default for SeparatorWidths {
+    fn default() -> Self {
+        Self {
+            line: Marking::DEFAULT_WIDTH,
+            space: Marking::DEFAULT_SPACE,
+            kerb: Marking::DEFAULT_WIDTH,
+        }
+    }
 }
 
 impl Locale {
@@ -23,9 +47,14 @@ impl Locale {
 
     #[must_use]
     #[allow(clippy::unused_self)]
-    pub fn travel_width(&self, designated: &Designated, _highway: HighwayType) -> Metre {
+    pub fn travel_width(&self, designated: &Designated, highway: HighwayType) -> Metre {
         match designated {
-            Designated::Motor | Designated::Bus => {
+            // Race tracks are much wider than public roads, guessed
+            Designated::Motor if highway == HighwayType::NonTravel(NonTravel::Raceway) => {
+                Metre::new(6.0)
+            },
+            Designated::Bus => self.bus_width(),
+            Designated::Motor => {
                 let uk = Country::the_united_kingdom_of_great_britain_and_northern_ireland();
                 match &self.country {
                     // Guessed, TODO: find DfT source.
@@ -36,10 +65,34 @@ impl Locale {
                 }
             },
             Designated::Foot => Metre::new(2.5),
-            Designated::Bicycle => Metre::new(2.0),
+            // Unless the lane is known to be painted, see `Lane::width`
+            Designated::Bicycle => self.bicycle_width(Cycleway::Track),
+            // A 2.65m wide tram and its swept path on curves, guessed
+            Designated::Rail => Metre::new(3.0),
         }
     }
 
+    /// Default width of a cycle track physically separated from the carriageway,
+    /// or of a narrower cycle lane painted on it
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn bicycle_width(&self, cycleway: Cycleway) -> Metre {
+        // Guessed
+        match cycleway {
+            Cycleway::Track => Metre::new(2.0),
+            Cycleway::Lane => Metre::new(1.5),
+        }
+    }
+
+    /// Default width of a dedicated bus lane or busway,
+    /// wider than a motor lane to fit a bus with its mirrors
+    #[must_use]
+    #[allow(clippy::unused_self)]
+    pub fn bus_width(&self) -> Metre {
+        // 12 ft, common for dedicated bus lanes, guessed to apply elsewhere
+        Metre::new(3.65)
+    }
+
     /// Road paint colour separating opposite directions of motor traffic
     /// default is white
     #[must_use]
@@ -73,6 +126,32 @@ impl Locale {
         }
     }
 
+    /// Narrowest carriageway of two lanes in opposite directions to have a centre line,
+    /// narrower residential streets often have none
+    #[must_use]
+    pub fn centre_line_min_width(&self) -> Metre {
+        match &self.country {
+            Some(c)
+                if c == &Country::the_united_kingdom_of_great_britain_and_northern_ireland() =>
+            {
+                // https://en.wikisource.org/wiki/Traffic_Signs_Manual/Chapter_5/2009/4
+                Metre::new(5.5)
+            },
+            // Guessed
+            _ => Metre::new(5.0),
+        }
+    }
+
+    /// Whether a speed without a unit, e.g. `maxspeed=30`, is in mph rather than km/h,
+    /// as in the United States and the United Kingdom
+    #[must_use]
+    pub fn speed_in_mph(&self) -> bool {
+        self.country.as_ref().map_or(false, |c| {
+            c == &Country::the_united_states_of_america()
+                || c == &Country::the_united_kingdom_of_great_britain_and_northern_ireland()
+        })
+    }
+
     /// Highway type with no `lanes=*` has:
     /// - (false) one lane with travel in both directions or
     /// - (true) two lanes with travel in opposite directions
@@ -94,6 +173,10 @@ impl Locale {
                     | HighwayImportance::Secondary
                     | HighwayImportance::Tertiary,
             ) | HighwayType::Residential
+                | HighwayType::Unclassified
+                // Unknown classification, assume a typical road
+                | HighwayType::UnknownRoad
+                | HighwayType::Busway
         )
     }
 
@@ -127,6 +210,7 @@ pub struct Config {
     iso_3166_2_subdivision: Option<String>,
     country: Option<Country>,
     driving_side: Option<DrivingSide>,
+    separator_widths: Option<SeparatorWidths>,
 }
 
 impl Config {
@@ -186,6 +270,12 @@ impl Config {
         self
     }
 
+    #[must_use]
+    pub fn separator_widths(mut self, widths: SeparatorWidths) -> Self {
+        self.separator_widths = Some(widths);
+        self
+    }
+
     #[must_use]
     pub fn build(&self) -> Locale {
         // TODO, more business logic
@@ -206,6 +296,7 @@ impl Config {
             country,
             iso_3166_2_subdivision: self.iso_3166_2_subdivision.clone(),
             driving_side: self.driving_side.unwrap_or(DrivingSide::Right),
+            separator_widths: self.separator_widths.unwrap_or_default(),
         }
     }
 }
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::locale::Locale;
+
 #[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Metre(f64);
 
@@ -60,6 +62,40 @@ pub enum Speed {
 }
 
 impl Speed {
+    /// Walking pace, the speed limit of `maxspeed=walk`,
+    /// see <https://wiki.openstreetmap.org/wiki/Key:maxspeed#Values>
+    pub const WALK: Self = Self::Kph(5.0);
+
+    /// Speed of a `maxspeed=*` value, `walk` or a number with an optional ` mph` or ` knots` unit.
+    /// Without a unit the number is in the unit of the locale, see [`Locale::speed_in_mph`].
+    /// The unit is kept, [`Speed::kph`] and [`Speed::as_mph`] convert between them.
+    ///
+    /// # Errors
+    ///
+    /// If the value is empty, not a number, or in an unknown unit
+    pub fn from_tag_value(value: &str, locale: &Locale) -> Result<Self, SpeedError> {
+        match value {
+            "walk" => Ok(Self::WALK),
+            value if locale.speed_in_mph() && !value.is_empty() && !value.contains(' ') => {
+                Ok(Self::Mph(value.parse()?))
+            },
+            value => value.parse(),
+        }
+    }
+
+    /// The `maxspeed=*` value of the speed, the inverse of [`Speed::from_tag_value`]:
+    /// `walk`, or a number in the unit of the locale, converting km/h to mph where needed.
+    /// Other units are written explicitly.
+    #[must_use]
+    pub fn to_tag_value(&self, locale: &Locale) -> String {
+        match self {
+            speed if *speed == Self::WALK => "walk".to_owned(),
+            Self::Kph(_) if locale.speed_in_mph() => self.as_mph().to_string(),
+            Self::Mph(val) if locale.speed_in_mph() => val.to_string(),
+            speed => speed.to_string(),
+        }
+    }
+
     #[must_use]
     pub fn kph(&self) -> f64 {
         match self {
@@ -68,6 +104,25 @@ impl Speed {
             Self::Knots(val) => 1.852_f64 * val,
         }
     }
+
+    #[must_use]
+    pub fn as_mph(&self) -> f64 {
+        match self {
+            Self::Mph(val) => *val,
+            Self::Kph(_) | Self::Knots(_) => self.kph() / 1.60934_f64,
+        }
+    }
+}
+
+impl PartialOrd for Speed {
+    /// Speeds are ordered by their value in km/h, regardless of unit.
+    /// Equal speeds in different units are unordered, as they are not equal.
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.kph().partial_cmp(&other.kph()) {
+            Some(std::cmp::Ordering::Equal) if self != other => None,
+            ordering => ordering,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -302,8 +357,63 @@ mod speed {
 #[allow(clippy::similar_names)]
 #[cfg(test)]
 mod tests {
+    use crate::locale::Locale;
     use crate::metric::Speed;
 
+    #[test]
+    fn speed_ordering() {
+        assert!(Speed::Kph(50.0) < Speed::Kph(60.0));
+        assert!(Speed::Mph(30.0) < Speed::Kph(50.0));
+        assert!(Speed::Mph(40.0) > Speed::Kph(50.0));
+        assert_eq!(Speed::Kph(1.852).partial_cmp(&Speed::Knots(1.0)), None);
+    }
+
+    #[test]
+    fn speed_from_tag_value() {
+        let germany = Locale::builder().iso_3166("DE").build();
+        let usa = Locale::builder().iso_3166("US").build();
+        for locale in [&germany, &usa] {
+            assert_eq!(
+                Speed::from_tag_value("30 mph", locale).unwrap(),
+                Speed::Mph(30.0)
+            );
+            assert_eq!(
+                Speed::from_tag_value("10 knots", locale).unwrap(),
+                Speed::Knots(10.0)
+            );
+            assert_eq!(Speed::from_tag_value("walk", locale).unwrap(), Speed::WALK);
+            assert!(Speed::from_tag_value("", locale).is_err());
+            assert!(Speed::from_tag_value("30 furlongs", locale).is_err());
+        }
+        assert_eq!(
+            Speed::from_tag_value("50", &germany).unwrap(),
+            Speed::Kph(50.0)
+        );
+        assert_eq!(Speed::from_tag_value("30", &usa).unwrap(), Speed::Mph(30.0));
+
+        let speed = Speed::from_tag_value("30 mph", &germany).unwrap();
+        assert!((speed.as_mph() - 30.0).abs() < f64::EPSILON);
+        assert_eq!(speed.to_string(), "30 mph");
+        assert!((Speed::Kph(80.4672).as_mph() - 50.0).abs() < 1e-9_f64);
+    }
+
+    #[test]
+    fn speed_to_tag_value() {
+        let germany = Locale::builder().iso_3166("DE").build();
+        let usa = Locale::builder().iso_3166("US").build();
+        for locale in [&germany, &usa] {
+            assert_eq!(Speed::WALK.to_tag_value(locale), "walk");
+            assert_eq!(Speed::Knots(10.0).to_tag_value(locale), "10 knots");
+        }
+        assert_eq!(Speed::Kph(50.0).to_tag_value(&germany), "50");
+        assert_eq!(Speed::Mph(30.0).to_tag_value(&germany), "30 mph");
+        assert_eq!(Speed::Mph(30.0).to_tag_value(&usa), "30");
+
+        let converted =
+            Speed::from_tag_value(&Speed::Kph(80.4672).to_tag_value(&usa), &usa).unwrap();
+        assert!(matches!(converted, Speed::Mph(mph) if (mph - 50.0).abs() < 1e-9_f64));
+    }
+
     #[test]
     fn test_speed() {
         let speed_kph_str = (Speed::Kph(1.0), "1.0");
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Designated, Direction, Lane, Marking, Road};
+use crate::locale::Locale;
+use crate::metric::Metre;
+
+/// What fills a part of the cross section
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Foot,
+    Bicycle,
+    Motor,
+    Bus,
+    Rail,
+    Parking,
+    Shoulder,
+    Separator,
+}
+
+/// A lane in the cross section
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Slice {
+    /// Offset of the left edge from the left edge of the road
+    pub offset: Metre,
+    pub width: Metre,
+    pub role: Role,
+    /// Direction of the arrows to draw, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub arrows: Option<Direction>,
+    /// Markings left to right, for separators
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub markings: Vec<Marking>,
+}
+
+/// Layout of a road across its width, left to right,
+/// for renderers that do not need to know about lanes
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CrossSection {
+    pub width: Metre,
+    pub slices: Vec<Slice>,
+}
+
+impl Road {
+    /// The road as a [`CrossSection`]
+    #[must_use]
+    pub fn cross_section(&self, locale: &Locale) -> CrossSection {
+        let slices = self
+            .lanes
+            .iter()
+            .zip(self.lane_offsets(locale))
+            .zip(self.lane_widths(locale))
+            .map(|((lane, offset), width)| {
+                let (role, arrows, markings) = match lane {
+                    Lane::Travel {
+                        designated,
+                        direction,
+                        ..
+                    } => {
+                        let role = match designated {
+                            Designated::Foot => Role::Foot,
+                            Designated::Bicycle => Role::Bicycle,
+                            Designated::Motor => Role::Motor,
+                            Designated::Bus => Role::Bus,
+                            Designated::Rail => Role::Rail,
+                        };
+                        (role, *direction, Vec::new())
+                    },
+                    Lane::Parking { .. } => (Role::Parking, None, Vec::new()),
+                    Lane::Shoulder { .. } => (Role::Shoulder, None, Vec::new()),
+                    Lane::Separator { markings } => (Role::Separator, None, markings.to_vec()),
+                };
+                Slice {
+                    offset,
+                    width,
+                    role,
+                    arrows,
+                    markings,
+                }
+            })
+            .collect();
+        CrossSection {
+            width: self.width(locale),
+            slices,
+        }
+    }
+}
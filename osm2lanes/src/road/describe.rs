@@ -0,0 +1,176 @@
+use super::{Designated, Direction, Lane, Road};
+use crate::locale::Locale;
+
+/// A count of lanes in words, e.g. "two lanes"
+fn lanes(count: usize) -> String {
+    let number = match count {
+        1 => "one",
+        2 => "two",
+        3 => "three",
+        4 => "four",
+        5 => "five",
+        6 => "six",
+        7 => "seven",
+        8 => "eight",
+        _ => return format!("{} lanes", count),
+    };
+    if count == 1 {
+        format!("{} lane", number)
+    } else {
+        format!("{} lanes", number)
+    }
+}
+
+impl Road {
+    /// A sentence describing the lanes of the road, for accessibility and summaries, e.g.
+    /// "A two-way road with one lane each direction, a bike lane on the right,
+    /// and sidewalks on both sides."
+    /// Sides are left and right of the motor and bus lanes, looking along the way.
+    #[must_use]
+    pub fn describe(&self, _locale: &Locale) -> String {
+        let carriageway: Vec<(usize, &Lane)> = self
+            .lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, lane)| {
+                matches!(
+                    lane,
+                    Lane::Travel {
+                        designated: Designated::Motor | Designated::Bus,
+                        ..
+                    }
+                )
+            })
+            .collect();
+        let count = |wanted: Direction| {
+            carriageway
+                .iter()
+                .filter(|(_, lane)| match lane {
+                    Lane::Travel { direction, .. } => *direction == Some(wanted),
+                    _ => false,
+                })
+                .count()
+        };
+        let mut parts = vec![match (
+            count(Direction::Forward),
+            count(Direction::Backward),
+            count(Direction::Both),
+        ) {
+            (0, 0, 0) => "A road without motor lanes".to_owned(),
+            (0, 0, 1) => "A road with a single lane shared by both directions".to_owned(),
+            (forward, backward, 0) if forward == 0 || backward == 0 => {
+                format!("A one-way road with {}", lanes(forward.max(backward)))
+            },
+            (forward, backward, 0) if forward == backward => {
+                format!("A two-way road with {} each direction", lanes(forward))
+            },
+            (forward, backward, 0) => format!(
+                "A two-way road with {} forward and {} backward",
+                lanes(forward),
+                lanes(backward)
+            ),
+            _ => format!("A two-way road with {}", lanes(carriageway.len())),
+        }];
+
+        let features: [(&str, &str, fn(&Lane) -> bool); 4] = [
+            ("a bike lane", "bike lanes", |lane| {
+                matches!(
+                    lane,
+                    Lane::Travel {
+                        designated: Designated::Bicycle,
+                        ..
+                    }
+                )
+            }),
+            ("parking", "parking", |lane| {
+                matches!(lane, Lane::Parking { .. })
+            }),
+            ("a shoulder", "shoulders", |lane| {
+                matches!(lane, Lane::Shoulder { .. })
+            }),
+            ("a sidewalk", "sidewalks", |lane| {
+                matches!(
+                    lane,
+                    Lane::Travel {
+                        designated: Designated::Foot,
+                        ..
+                    }
+                )
+            }),
+        ];
+        for (one, both, is_feature) in features {
+            let on_side = |outside: &dyn Fn(usize) -> bool| {
+                self.lanes
+                    .iter()
+                    .enumerate()
+                    .any(|(index, lane)| is_feature(lane) && outside(index))
+            };
+            let left = on_side(&|index| {
+                carriageway
+                    .first()
+                    .map_or(false, |(first, _)| index < *first)
+            });
+            let right =
+                on_side(&|index| carriageway.last().map_or(false, |(last, _)| index > *last));
+            match (left, right) {
+                (true, true) => parts.push(format!("{} on both sides", both)),
+                (true, false) => parts.push(format!("{} on the left", one)),
+                (false, true) => parts.push(format!("{} on the right", one)),
+                (false, false) => {},
+            }
+        }
+
+        match parts.split_last() {
+            None => String::new(),
+            Some((last, [])) => format!("{}.", last),
+            Some((last, [first])) => format!("{} and {}.", first, last),
+            Some((last, init)) => format!("{}, and {}.", init.join(", "), last),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    fn describe(tags: &[[&str; 2]]) -> String {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        tags_to_lanes(
+            &Tags::from_str_pairs(tags).unwrap(),
+            &locale,
+            &TagsToLanesConfig::default(),
+        )
+        .unwrap()
+        .road
+        .describe(&locale)
+    }
+
+    #[test]
+    fn two_way_with_bike_lane_and_sidewalks() {
+        assert_eq!(
+            describe(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["cycleway:right", "lane"],
+                ["sidewalk", "both"],
+            ]),
+            "A two-way road with one lane each direction, a bike lane on the right, \
+            and sidewalks on both sides."
+        );
+    }
+
+    #[test]
+    fn one_way_with_parking() {
+        let description = describe(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["parking:lane:left", "parallel"],
+        ]);
+        assert!(description.starts_with("A one-way road with two lanes"));
+        assert!(description.contains("parking on the left"));
+        assert!(!description.contains("sidewalk"));
+    }
+}
@@ -0,0 +1,94 @@
+use super::{Lane, Printable, Road};
+use crate::locale::Locale;
+
+/// Labels of lanes for display, such as a glyph or a word in the language of the locale
+pub trait LaneLabeler {
+    fn label(&self, lane: &Lane, locale: &Locale) -> String;
+}
+
+/// Labels from [`Printable::as_utf8`], e.g. "🚲"
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Utf8Labeler;
+
+impl LaneLabeler for Utf8Labeler {
+    fn label(&self, lane: &Lane, _locale: &Locale) -> String {
+        lane.as_utf8().to_string()
+    }
+}
+
+/// Labels from [`Printable::as_ascii`], e.g. "b"
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AsciiLabeler;
+
+impl LaneLabeler for AsciiLabeler {
+    fn label(&self, lane: &Lane, _locale: &Locale) -> String {
+        lane.as_ascii().to_string()
+    }
+}
+
+impl Road {
+    /// A label for each lane, left to right
+    #[must_use]
+    pub fn labels(&self, labeler: &dyn LaneLabeler, locale: &Locale) -> Vec<String> {
+        self.lanes
+            .iter()
+            .map(|lane| labeler.label(lane, locale))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsciiLabeler, LaneLabeler, Utf8Labeler};
+    use crate::locale::{DrivingSide, Locale};
+    use crate::road::{Designated, Lane, Road};
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    struct Words;
+
+    impl LaneLabeler for Words {
+        fn label(&self, lane: &Lane, _locale: &Locale) -> String {
+            match lane {
+                Lane::Travel {
+                    designated: Designated::Foot,
+                    ..
+                } => "sidewalk",
+                Lane::Travel { .. } => "lane",
+                Lane::Shoulder { .. } => "shoulder",
+                Lane::Parking { .. } => "parking",
+                Lane::Separator { .. } => "-",
+            }
+            .to_owned()
+        }
+    }
+
+    fn road(locale: &Locale) -> Road {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["sidewalk", "right"],
+        ])
+        .unwrap();
+        tags_to_lanes(&tags, locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road
+    }
+
+    #[test]
+    fn default_labels() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = road(&locale);
+        assert_eq!(road.labels(&AsciiLabeler, &locale), vec!["d", "d", "s"]);
+        assert_eq!(road.labels(&Utf8Labeler, &locale), vec!["🚗", "🚗", "🚶"]);
+    }
+
+    #[test]
+    fn custom_labels() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        assert_eq!(
+            road(&locale).labels(&Words, &locale),
+            vec!["lane", "lane", "sidewalk"]
+        );
+    }
+}
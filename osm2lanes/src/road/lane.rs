@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::Markings;
+use super::{Markings, TurnDirection};
 use crate::locale::Locale;
 use crate::metric::{Metre, Speed};
 use crate::tag::{Access as AccessValue, HighwayType};
@@ -18,18 +18,54 @@ pub enum Lane {
         width: Option<Metre>,
         #[serde(skip_serializing_if = "Option::is_none")]
         max_speed: Option<Speed>,
+        /// Recommended speed, not the legal limit,
+        /// see <https://wiki.openstreetmap.org/wiki/Key:maxspeed:advisory>
+        #[serde(skip_serializing_if = "Option::is_none")]
+        advisory_speed: Option<Speed>,
         #[serde(skip_serializing_if = "Option::is_none")]
         access: Option<Access>,
+        /// Raw conditional restriction,
+        /// e.g. `designated @ (Mo-Fr 07:00-09:00)` for a peak hour bus lane
+        #[serde(skip_serializing_if = "Option::is_none")]
+        conditional: Option<String>,
+        /// Steps of a `highway=steps`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        steps: Option<Steps>,
+        /// Kind of a cycle lane, painted or a separate track
+        #[serde(skip_serializing_if = "Option::is_none")]
+        cycleway: Option<Cycleway>,
+        /// Kind of a `highway=footway`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        footway: Option<Footway>,
+        /// Turn markings from `turn:lanes=*`, in the direction of travel
+        #[serde(skip_serializing_if = "Option::is_none")]
+        turn: Option<Vec<TurnDirection>>,
     },
     Parking {
         direction: Direction,
         designated: Designated,
         #[serde(skip_serializing_if = "Option::is_none")]
         width: Option<Metre>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        orientation: Option<ParkingOrientation>,
+        /// Raw conditional restriction, e.g. `no @ (Mo-Fr 07:00-09:00)`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        conditional: Option<String>,
+        /// Raw `parking:condition` value, e.g. `ticket` or `residents`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        condition: Option<String>,
+        /// Whether parking here costs a fee
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fee: Option<bool>,
     },
     Shoulder {
         #[serde(skip_serializing_if = "Option::is_none")]
         width: Option<Metre>,
+        /// Raw OSM surface value, e.g. `asphalt`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        surface: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        access: Option<Access>,
     },
     Separator {
         markings: Markings,
@@ -45,19 +81,36 @@ impl Lane {
     #[must_use]
     pub fn width(&self, locale: &Locale, highway: HighwayType) -> Metre {
         match self {
-            Lane::Separator { markings } => markings.width(locale),
             Lane::Travel {
-                width, designated, ..
-            } => width.unwrap_or_else(|| locale.travel_width(designated, highway)),
+                width: Some(width), ..
+            }
+            | Lane::Parking {
+                width: Some(width), ..
+            }
+            | Lane::Shoulder {
+                width: Some(width), ..
+            } => *width,
+            _ => self.default_width(locale, highway),
+        }
+    }
+
+    /// Width in metres of the lane without a width, under `locale`
+    pub(super) fn default_width(&self, locale: &Locale, highway: HighwayType) -> Metre {
+        match self {
+            Lane::Separator { markings } => markings.width(locale),
             // TODO: parking different from travel?
-            Lane::Parking {
-                width, designated, ..
-            } => width.unwrap_or_else(|| locale.travel_width(designated, highway)),
-            Lane::Shoulder { width, .. } => width.unwrap_or(Lane::DEFAULT_WIDTH),
+            Lane::Travel {
+                cycleway: Some(cycleway),
+                ..
+            } => locale.bicycle_width(*cycleway),
+            Lane::Travel { designated, .. } | Lane::Parking { designated, .. } => {
+                locale.travel_width(designated, highway)
+            },
+            Lane::Shoulder { .. } => Lane::DEFAULT_WIDTH,
         }
     }
 
-    /// Mirror the lane
+    /// Mirror the lane, flipping left and right but keeping its direction
     #[must_use]
     pub fn mirror(self) -> Self {
         match self {
@@ -65,9 +118,279 @@ impl Lane {
                 markings.flip();
                 Self::Separator { markings }
             },
+            Self::Travel {
+                direction,
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access,
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn,
+            } => Self::Travel {
+                direction,
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access,
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn: turn.map(|turn| turn.into_iter().map(TurnDirection::mirror).collect()),
+            },
+            _ => self,
+        }
+    }
+
+    /// The lane described along the reversed way,
+    /// mirrored and travelling in the opposite direction
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        match self {
+            Self::Travel {
+                direction,
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access,
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn,
+            } => Self::Travel {
+                direction: direction.map(Direction::reverse),
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access,
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn,
+            },
+            Self::Parking {
+                direction,
+                designated,
+                width,
+                orientation,
+                conditional,
+                condition,
+                fee,
+            } => Self::Parking {
+                direction: direction.reverse(),
+                designated,
+                width,
+                orientation,
+                conditional,
+                condition,
+                fee,
+            },
+            lane => lane.mirror(),
+        }
+    }
+
+    /// Equality with widths and speeds within tolerance, see `Road::approx_eq`
+    #[must_use]
+    pub(super) fn approx_eq(&self, other: &Self, width_tol: Metre, speed_tol: Speed) -> bool {
+        let width_eq = |a: &Option<Metre>, b: &Option<Metre>| match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => (a.val() - b.val()).abs() <= width_tol.val(),
+            (None, Some(_)) | (Some(_), None) => false,
+        };
+        let speed_eq = |a: &Option<Speed>, b: &Option<Speed>| match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => (a.kph() - b.kph()).abs() <= speed_tol.kph(),
+            (None, Some(_)) | (Some(_), None) => false,
+        };
+        match (self, other) {
+            (
+                Self::Travel {
+                    direction,
+                    designated,
+                    width,
+                    max_speed,
+                    advisory_speed,
+                    access,
+                    conditional,
+                    steps,
+                    cycleway,
+                    footway,
+                    turn,
+                },
+                Self::Travel {
+                    direction: other_direction,
+                    designated: other_designated,
+                    width: other_width,
+                    max_speed: other_max_speed,
+                    advisory_speed: other_advisory_speed,
+                    access: other_access,
+                    conditional: other_conditional,
+                    steps: other_steps,
+                    cycleway: other_cycleway,
+                    footway: other_footway,
+                    turn: other_turn,
+                },
+            ) => {
+                direction == other_direction
+                    && designated == other_designated
+                    && width_eq(width, other_width)
+                    && speed_eq(max_speed, other_max_speed)
+                    && speed_eq(advisory_speed, other_advisory_speed)
+                    && access == other_access
+                    && conditional == other_conditional
+                    && steps == other_steps
+                    && cycleway == other_cycleway
+                    && footway == other_footway
+                    && turn == other_turn
+            },
+            (
+                Self::Parking {
+                    direction,
+                    designated,
+                    width,
+                    orientation,
+                    conditional,
+                    condition,
+                    fee,
+                },
+                Self::Parking {
+                    direction: other_direction,
+                    designated: other_designated,
+                    width: other_width,
+                    orientation: other_orientation,
+                    conditional: other_conditional,
+                    condition: other_condition,
+                    fee: other_fee,
+                },
+            ) => {
+                direction == other_direction
+                    && designated == other_designated
+                    && width_eq(width, other_width)
+                    && orientation == other_orientation
+                    && conditional == other_conditional
+                    && condition == other_condition
+                    && fee == other_fee
+            },
+            (
+                Self::Shoulder {
+                    width,
+                    surface,
+                    access,
+                },
+                Self::Shoulder {
+                    width: other_width,
+                    surface: other_surface,
+                    access: other_access,
+                },
+            ) => width_eq(width, other_width) && surface == other_surface && access == other_access,
+            (Self::Separator { markings }, Self::Separator { markings: other }) => {
+                markings.len() == other.len()
+                    && markings.iter().zip(other.iter()).all(|(marking, other)| {
+                        marking.style == other.style
+                            && marking.color == other.color
+                            && width_eq(&marking.width, &other.width)
+                    })
+            },
+            _ => false,
+        }
+    }
+
+    /// Remove trivial differences, see `Road::canonicalize`
+    #[must_use]
+    pub(super) fn canonicalize(self) -> Self {
+        match self {
+            Self::Travel {
+                direction,
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access,
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn,
+            } => Self::Travel {
+                direction,
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access: access.filter(|access| !access.is_empty()),
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn,
+            },
+            Self::Shoulder {
+                width,
+                surface,
+                access,
+            } => Self::Shoulder {
+                width,
+                surface,
+                access: access.filter(|access| !access.is_empty()),
+            },
             _ => self,
         }
     }
+
+    /// A lane in both directions in place of this and the adjacent travel lane,
+    /// if they are in opposite directions and otherwise the same, see `Road::canonicalize`
+    pub(super) fn merge_opposing(&self, other: &Self) -> Option<Self> {
+        let (width, other_width) = match (self, other) {
+            (
+                Self::Travel {
+                    direction: Some(direction),
+                    width,
+                    ..
+                },
+                Self::Travel {
+                    direction: Some(other_direction),
+                    width: other_width,
+                    ..
+                },
+            ) if matches!(
+                (direction, other_direction),
+                (Direction::Forward, Direction::Backward)
+                    | (Direction::Backward, Direction::Forward)
+            ) =>
+            {
+                (*width, *other_width)
+            },
+            _ => return None,
+        };
+        let width = match (width, other_width) {
+            (Some(width), Some(other_width)) => Some(width + other_width),
+            _ => None,
+        };
+        let both = |lane: &Self| {
+            let mut lane = lane.clone();
+            if let Self::Travel {
+                direction,
+                width: lane_width,
+                ..
+            } = &mut lane
+            {
+                *direction = Some(Direction::Both);
+                *lane_width = width;
+            }
+            lane
+        };
+        let merged = both(self);
+        (merged == both(other)).then(|| merged)
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -78,6 +401,102 @@ pub enum Direction {
     Both,
 }
 
+impl Direction {
+    /// The opposite direction, `Both` stays the same
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        match self {
+            Self::Forward => Self::Backward,
+            Self::Backward => Self::Forward,
+            Self::Both => Self::Both,
+        }
+    }
+}
+
+/// A flight of steps, see <https://wiki.openstreetmap.org/wiki/Tag:highway%3Dsteps>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Steps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step_count: Option<u32>,
+    /// Whether there is a handrail, `handrail=*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handrail: Option<bool>,
+    /// Whether there is a ramp beside the steps, e.g. for pushing bicycles, `ramp=*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ramp: Option<bool>,
+}
+
+/// Kind of cycle lane, see <https://wiki.openstreetmap.org/wiki/Key:cycleway>
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cycleway {
+    /// Painted on the carriageway, `cycleway=lane`
+    Lane,
+    /// Physically separated from the carriageway, `cycleway=track`
+    Track,
+}
+
+impl Cycleway {
+    /// The `cycleway=*` value
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Lane => "lane",
+            Self::Track => "track",
+        }
+    }
+}
+
+/// Kind of footway, see <https://wiki.openstreetmap.org/wiki/Key:footway>
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Footway {
+    /// Beside a road, `footway=sidewalk`
+    Sidewalk,
+    /// Across a road, `footway=crossing`
+    Crossing,
+    /// Refuge within a crossing, `footway=traffic_island`
+    TrafficIsland,
+    /// Beside parking spaces, `footway=access_aisle`
+    AccessAisle,
+}
+
+impl Footway {
+    /// The `footway=*` value
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Sidewalk => "sidewalk",
+            Self::Crossing => "crossing",
+            Self::TrafficIsland => "traffic_island",
+            Self::AccessAisle => "access_aisle",
+        }
+    }
+}
+
+impl std::str::FromStr for Footway {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sidewalk" => Ok(Self::Sidewalk),
+            "crossing" => Ok(Self::Crossing),
+            "traffic_island" => Ok(Self::TrafficIsland),
+            "access_aisle" => Ok(Self::AccessAisle),
+            _ => Err(s.to_owned()),
+        }
+    }
+}
+
+/// How vehicles are parked relative to the lane,
+/// see <https://wiki.openstreetmap.org/wiki/Key:parking:lane>
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParkingOrientation {
+    Parallel,
+    Diagonal,
+    Perpendicular,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Designated {
     // #[serde(rename = "any")]
@@ -90,6 +509,9 @@ pub enum Designated {
     Motor,
     #[serde(rename = "bus")]
     Bus,
+    /// Tram or light rail tracks, see <https://wiki.openstreetmap.org/wiki/Tag:railway%3Dtram>
+    #[serde(rename = "rail")]
+    Rail,
 }
 
 /// Display lane detail as printable characters
@@ -117,6 +539,10 @@ impl Printable for Lane {
                 designated: Designated::Bus,
                 ..
             } => 'B',
+            Self::Travel {
+                designated: Designated::Rail,
+                ..
+            } => 't',
             Self::Shoulder { .. } => 'S',
             Self::Parking { .. } => 'p',
             Self::Separator { .. } => '|',
@@ -140,6 +566,10 @@ impl Printable for Lane {
                 designated: Designated::Bus,
                 ..
             } => '🚌',
+            Self::Travel {
+                designated: Designated::Rail,
+                ..
+            } => '🚊',
             Self::Shoulder { .. } => '🛆',
             Self::Parking { .. } => '🅿',
             Self::Separator { .. } => '|',
@@ -180,4 +610,21 @@ pub struct Access {
     pub(crate) bus: Option<AccessValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) motor: Option<AccessValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) hgv: Option<AccessValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) goods: Option<AccessValue>,
+}
+
+impl Access {
+    /// No access tags were set for any mode
+    fn is_empty(&self) -> bool {
+        self.foot.is_none()
+            && self.bicycle.is_none()
+            && self.taxi.is_none()
+            && self.bus.is_none()
+            && self.motor.is_none()
+            && self.hgv.is_none()
+            && self.goods.is_none()
+    }
 }
@@ -14,6 +14,7 @@ impl Markings {
     }
 
     /// Flip left and right, reverses the order of markings and inverts them in place.
+    /// The widths of the markings, and so the total width, are kept.
     pub fn flip(&mut self) {
         self.0.reverse();
         for marking in &mut self.0 {
@@ -21,12 +22,24 @@ impl Markings {
         }
     }
 
+    /// Set the width of every marking to zero, for separators that are only lane boundaries
+    pub fn zero_widths(&mut self) {
+        for marking in &mut self.0 {
+            marking.width = Some(Metre::new(0.0));
+        }
+    }
+
+    /// Append markings to the right of these
+    pub fn append(&mut self, mut other: Self) {
+        self.0.append(&mut other.0);
+    }
+
     /// Width in metres
     #[must_use]
-    pub fn width(&self, _locale: &Locale) -> Metre {
+    pub fn width(&self, locale: &Locale) -> Metre {
         self.0
             .iter()
-            .map(|marking| marking.width.unwrap_or(Marking::DEFAULT_WIDTH))
+            .map(|marking| marking.width.unwrap_or(locale.separator_widths.line))
             .sum::<Metre>()
     }
 }
@@ -79,6 +92,9 @@ pub enum Style {
     KerbUp,
     #[serde(rename = "kerb_down")]
     KerbDown,
+    /// Raised median or traffic island, kerbed on both sides
+    #[serde(rename = "median")]
+    Median,
 }
 
 impl Style {
@@ -93,6 +109,7 @@ impl Style {
             Self::KerbDown => '\\',
             Self::KerbUp => '/',
             Self::NoFill => ' ',
+            Self::Median => '█',
         }
     }
     /// Opposite marking style
@@ -106,6 +123,7 @@ impl Style {
             Self::KerbDown => Self::KerbUp,
             Self::KerbUp => Self::KerbDown,
             Self::NoFill => Self::NoFill,
+            Self::Median => Self::Median,
         }
     }
 }
@@ -1,36 +1,1537 @@
+use std::{iter, mem};
+
 use serde::{Deserialize, Serialize};
 
-use crate::locale::Locale;
-use crate::metric::Metre;
-use crate::tag::Highway;
+use crate::locale::{DrivingSide, Locale};
+use crate::metric::{Metre, Speed};
+use crate::tag::{Access as AccessValue, Highway, HighwayImportance, HighwayType};
 
 mod lane;
-pub use lane::{Access, Designated, Direction, Lane, Printable};
+pub use lane::{
+    Access, Cycleway, Designated, Direction, Footway, Lane, ParkingOrientation, Printable, Steps,
+};
 
 mod marking;
 pub use marking::{Color, Marking, Markings, Style};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+mod turn;
+pub use turn::{TurnDirection, UnknownTurn};
+
+mod cross_section;
+pub use cross_section::{CrossSection, Role, Slice};
+
+mod describe;
+
+mod label;
+pub use label::{AsciiLabeler, LaneLabeler, Utf8Labeler};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Road {
     pub lanes: Vec<Lane>,
     #[serde(flatten)]
     pub highway: Highway,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bus_bay: Option<BusBay>,
+    /// Raw OSM surface value of the road, e.g. `gravel`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+    /// Weight limit in tonnes, from `maxweight=*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_weight: Option<f64>,
+    /// Speed limit for heavy goods vehicles, from `maxspeed:hgv=*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_speed_hgv: Option<Speed>,
+    /// Speed limit for buses, from `maxspeed:bus=*`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_speed_bus: Option<Speed>,
+    /// Firmness of a track from `tracktype=grade<N>`, 1 (paved) to 5 (soft)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub track_grade: Option<u8>,
+    /// Raw OSM conditional direction of a tidal road from `oneway:conditional=*`,
+    /// e.g. `-1 @ (Mo-Fr 16:00-19:00)`. The lanes are in the default direction from `oneway=*`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oneway_conditional: Option<String>,
+    /// Vertical order of stacked ways from `layer=*`, e.g. `-1` below ground level,
+    /// see <https://wiki.openstreetmap.org/wiki/Key:layer>
+    #[serde(default)]
+    pub layer: i32,
+    /// A plaza rather than a linear way, `highway=pedestrian` + `area=yes`,
+    /// see <https://wiki.openstreetmap.org/wiki/Tag:highway%3Dpedestrian#Squares_and_plazas>.
+    /// Its single foot lane is the whole area, so should not be drawn as a strip.
+    #[serde(default)]
+    pub is_area: bool,
+    /// Locale that the defaulted lane widths were derived from,
+    /// see [`Road::recompute_defaults`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    defaults_locale: Option<Locale>,
+    /// Whether the width of each lane, left to right, was defaulted rather than tagged,
+    /// see [`Road::recompute_defaults`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    defaulted_widths: Vec<bool>,
+}
+
+/// Equality of the road itself, regardless of where its defaults came from
+impl PartialEq for Road {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            lanes,
+            highway,
+            bus_bay,
+            surface,
+            max_weight,
+            max_speed_hgv,
+            max_speed_bus,
+            track_grade,
+            oneway_conditional,
+            layer,
+            is_area,
+            defaults_locale: _,
+            defaulted_widths: _,
+        } = self;
+        *lanes == other.lanes
+            && *highway == other.highway
+            && *bus_bay == other.bus_bay
+            && *surface == other.surface
+            && *max_weight == other.max_weight
+            && *max_speed_hgv == other.max_speed_hgv
+            && *max_speed_bus == other.max_speed_bus
+            && *track_grade == other.track_grade
+            && *oneway_conditional == other.oneway_conditional
+            && *layer == other.layer
+            && *is_area == other.is_area
+    }
+}
+
+/// Pull-in bus stop that locally widens the road,
+/// see <https://wiki.openstreetmap.org/wiki/Key:bus_bay>.
+/// The side is relative to the way, not the driving side.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BusBay {
+    Left,
+    Right,
+    Both,
+}
+
+impl BusBay {
+    const fn mirror(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+            Self::Both => Self::Both,
+        }
+    }
+}
+
+/// Side of the road's centre line, looking along the way
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    Left,
+    Right,
+    /// On the centre line, such as a shared centre turn lane or the separator between directions
+    Centre,
+}
+
+/// Motor or bus lanes out of order by direction, see [`Road::check_direction_order`]
+#[derive(Debug, PartialEq)]
+pub struct LaneOrderError {
+    /// Index of the first lane out of order
+    pub index: usize,
+}
+
+impl std::fmt::Display for LaneOrderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "lane {} is out of order by direction", self.index)
+    }
+}
+
+impl std::error::Error for LaneOrderError {}
+
+/// Implausible lanes of a road, see [`Road::validate`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationWarning {
+    /// Parking lane at the index between motor lanes rather than at the edge of the road
+    ParkingBetweenMotorLanes { index: usize },
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParkingBetweenMotorLanes { index } => {
+                write!(f, "parking lane {} is between motor lanes", index)
+            },
+        }
+    }
+}
+
+/// Reason two roads cannot be merged, see [`Road::try_merge`]
+#[derive(Debug, PartialEq)]
+pub enum MergeError {
+    /// The roads have a different number of lanes
+    LaneCount { left: usize, right: usize },
+    /// The lanes at the index differ
+    Lane { index: usize },
+    /// A property of the whole road differs, such as the highway or surface
+    Road,
 }
 
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LaneCount { left, right } => {
+                write!(f, "roads have {} and {} lanes", left, right)
+            },
+            Self::Lane { index } => write!(f, "lane {} differs", index),
+            Self::Road => write!(f, "road properties differ"),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
 impl Road {
+    /// A road from lanes left to right, checking their order with [`Road::check_direction_order`]
+    ///
+    /// # Errors
+    ///
+    /// If the motor or bus lanes are out of order by direction
+    pub fn new(
+        lanes: Vec<Lane>,
+        highway: Highway,
+        locale: &Locale,
+    ) -> Result<Self, LaneOrderError> {
+        let road = Self::from_lanes(lanes, highway);
+        road.check_direction_order(locale)?;
+        Ok(road)
+    }
+
+    /// A road from lanes left to right, without checking their order, see [`Road::new`]
+    #[must_use]
+    pub fn from_lanes(lanes: Vec<Lane>, highway: Highway) -> Self {
+        Self {
+            lanes,
+            highway,
+            bus_bay: None,
+            surface: None,
+            max_weight: None,
+            max_speed_hgv: None,
+            max_speed_bus: None,
+            track_grade: None,
+            oneway_conditional: None,
+            layer: 0,
+            is_area: false,
+            defaults_locale: None,
+            defaulted_widths: Vec::new(),
+        }
+    }
+
+    /// Check that the motor and bus lanes go from backward, through both ways, to forward,
+    /// left to right when driving on the right and right to left when driving on the left.
+    /// Other lanes, such as contraflow cycle lanes, may be in any direction.
+    ///
+    /// # Errors
+    ///
+    /// The first lane travelling in a direction that belongs further inside
+    pub fn check_direction_order(&self, locale: &Locale) -> Result<(), LaneOrderError> {
+        let rank =
+            |direction: Direction| match (locale.driving_side, direction) {
+                (DrivingSide::Right, Direction::Backward)
+                | (DrivingSide::Left, Direction::Forward) => 0_u8,
+                (_, Direction::Both) => 1,
+                (DrivingSide::Right, Direction::Forward)
+                | (DrivingSide::Left, Direction::Backward) => 2,
+            };
+        let mut previous = 0_u8;
+        for (index, lane) in self.lanes.iter().enumerate() {
+            if let Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                direction: Some(direction),
+                ..
+            } = lane
+            {
+                let rank = rank(*direction);
+                if rank < previous {
+                    return Err(LaneOrderError { index });
+                }
+                previous = rank;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check for arrangements of lanes that are almost always a parsing error,
+    /// such as a parking lane between motor lanes.
+    #[must_use]
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let is_motor = |lane: &Lane| {
+            matches!(
+                lane,
+                Lane::Travel {
+                    designated: Designated::Motor | Designated::Bus,
+                    ..
+                }
+            )
+        };
+        self.lanes
+            .iter()
+            .enumerate()
+            .filter(|(_, lane)| matches!(lane, Lane::Parking { .. }))
+            .filter(|(index, _)| {
+                let (inside, outside) = self.lanes.split_at(*index);
+                inside.iter().any(is_motor) && outside.iter().skip(1).any(is_motor)
+            })
+            .map(|(index, _)| ValidationWarning::ParkingBetweenMotorLanes { index })
+            .collect()
+    }
+
     #[must_use]
     pub fn has_separators(&self) -> bool {
         self.lanes.iter().any(Lane::is_separator)
     }
+
+    /// The separators with their index among all lanes, left to right
+    pub fn separators(&self) -> impl Iterator<Item = (usize, &Markings)> {
+        self.lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, lane)| match lane {
+                Lane::Separator { markings } => Some((index, markings)),
+                _ => None,
+            })
+    }
+
+    #[must_use]
+    pub fn num_separators(&self) -> usize {
+        self.separators().count()
+    }
+
+    /// The road with all separators removed,
+    /// to compare lanes regardless of whether separators were included
+    #[must_use]
+    pub fn without_separators(mut self) -> Self {
+        self.remove_separators();
+        self
+    }
+
+    /// Remove all separators in place, see [`Road::without_separators`]
+    pub(crate) fn remove_separators(&mut self) {
+        self.retain_lanes(|lane| !lane.is_separator());
+    }
+
+    /// Keep only the lanes for which `keep` is true,
+    /// along with whether their widths were defaulted, see [`Road::defaulted_widths`]
+    pub fn retain_lanes(&mut self, mut keep: impl FnMut(&Lane) -> bool) {
+        if self.defaulted_widths.is_empty() {
+            self.lanes.retain(keep);
+            return;
+        }
+        let defaulted = mem::take(&mut self.defaulted_widths)
+            .into_iter()
+            .chain(iter::repeat(false));
+        let (lanes, defaulted_widths) = mem::take(&mut self.lanes)
+            .into_iter()
+            .zip(defaulted)
+            .filter(|(lane, _defaulted)| keep(lane))
+            .unzip();
+        self.lanes = lanes;
+        self.defaulted_widths = defaulted_widths;
+    }
+
+    /// Replace the lanes with the same lanes between other separators,
+    /// keeping whether their widths were defaulted, see [`Road::defaulted_widths`]
+    pub(crate) fn replace_separators(&mut self, lanes: Vec<Lane>) {
+        if !self.defaulted_widths.is_empty() {
+            let defaulted: Vec<bool> = self
+                .lanes
+                .iter()
+                .zip(self.defaulted_widths.iter().chain(iter::repeat(&false)))
+                .filter(|(lane, _defaulted)| !lane.is_separator())
+                .map(|(_lane, defaulted)| *defaulted)
+                .collect();
+            let mut defaulted = defaulted.into_iter();
+            self.defaulted_widths = lanes
+                .iter()
+                .map(|lane| !lane.is_separator() && defaulted.next().unwrap_or(false))
+                .collect();
+        }
+        self.lanes = lanes;
+    }
+
+    /// Runs of equal adjacent lanes, left to right, with the number of lanes in each,
+    /// e.g. to summarize many motor lanes in a compact display.
+    /// Separators break up runs, see [`Road::without_separators`].
+    #[must_use]
+    #[allow(clippy::integer_arithmetic)]
+    pub fn collapsed_view(&self) -> Vec<(Lane, usize)> {
+        let mut runs: Vec<(Lane, usize)> = Vec::new();
+        for lane in &self.lanes {
+            match runs.last_mut() {
+                Some((run, count)) if run == lane => *count += 1,
+                _ => runs.push((lane.clone(), 1)),
+            }
+        }
+        runs
+    }
+
+    /// Whether `mode` may legally travel along the road on any of its travel lanes.
+    /// Access tagged on a lane wins, otherwise a lane is open to its designated mode,
+    /// and motor lanes are also open to buses, and to bicycles except on motorways.
+    /// Pedestrians walk on the carriageway only when there is no foot lane,
+    /// again except on motorways.
+    #[must_use]
+    pub fn is_accessible_to(&self, mode: Designated) -> bool {
+        let motorway = matches!(
+            self.highway.r#type(),
+            HighwayType::Classified(HighwayImportance::Motorway)
+                | HighwayType::Link(HighwayImportance::Motorway)
+        );
+        let foot_lane = self.lanes.iter().any(|lane| {
+            matches!(
+                lane,
+                Lane::Travel {
+                    designated: Designated::Foot,
+                    ..
+                }
+            )
+        });
+        self.lanes.iter().any(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                ..
+            } if mode == Designated::Foot && foot_lane => false,
+            Lane::Travel {
+                designated, access, ..
+            } => {
+                let tagged = access.as_ref().and_then(|access| match mode {
+                    Designated::Foot => access.foot.as_ref(),
+                    Designated::Bicycle => access.bicycle.as_ref(),
+                    Designated::Motor => access.motor.as_ref(),
+                    Designated::Bus => access.bus.as_ref(),
+                    Designated::Rail => None,
+                });
+                match tagged {
+                    Some(AccessValue::No | AccessValue::Private) => false,
+                    Some(_) => true,
+                    None => match (*designated, mode) {
+                        (Designated::Motor, Designated::Bus) => true,
+                        (Designated::Motor, Designated::Bicycle) => !motorway,
+                        (Designated::Motor | Designated::Bus, Designated::Foot) => !motorway,
+                        (designated, mode) => designated == mode,
+                    },
+                }
+            },
+            Lane::Parking { .. } | Lane::Shoulder { .. } | Lane::Separator { .. } => false,
+        })
+    }
 }
 
 impl Road {
+    /// Locale that the defaulted lane widths were derived from, if known,
+    /// see [`Road::recompute_defaults`]
+    #[must_use]
+    pub fn defaults_locale(&self) -> Option<&Locale> {
+        self.defaults_locale.as_ref()
+    }
+
+    /// Whether the width of each lane, left to right, was defaulted rather than tagged,
+    /// or empty if not known, e.g. for a road not made from tags
+    #[must_use]
+    pub fn defaulted_widths(&self) -> &[bool] {
+        &self.defaulted_widths
+    }
+
+    /// Record the locale that the widths of the lanes, left to right, were defaulted under
+    pub(crate) fn set_defaults(&mut self, locale: &Locale, defaulted_widths: Vec<bool>) {
+        self.defaults_locale = Some(locale.clone());
+        self.defaulted_widths = defaulted_widths;
+    }
+
+    /// Re-derive the lane widths defaulted under [`Road::defaults_locale`] for `locale`,
+    /// e.g. to display a road parsed under one locale under another.
+    /// Only the travel and parking lanes in [`Road::defaulted_widths`] are changed,
+    /// tagged widths are kept.
+    /// Speeds are never defaulted, only read from tags, so are left as they are.
+    pub fn recompute_defaults(&mut self, locale: &Locale) {
+        let highway = self.highway.r#type();
+        for (lane, defaulted) in self.lanes.iter_mut().zip(&self.defaulted_widths) {
+            if !defaulted {
+                continue;
+            }
+            let default = lane.default_width(locale, highway);
+            if let Lane::Travel { width, .. } | Lane::Parking { width, .. } = lane {
+                *width = Some(default);
+            }
+        }
+        self.defaults_locale = Some(locale.clone());
+    }
+
+    /// Width of each lane in metres, left to right, including separators
+    #[must_use]
+    pub fn lane_widths(&self, locale: &Locale) -> Vec<Metre> {
+        self.lanes
+            .iter()
+            .map(|lane| lane.width(locale, self.highway.r#type()))
+            .collect()
+    }
+
     /// Width in metres
     #[must_use]
     pub fn width(&self, locale: &Locale) -> Metre {
+        self.lane_widths(locale).into_iter().sum::<Metre>()
+    }
+
+    /// Offset of the left edge of each lane in metres from the left edge of the road,
+    /// left to right, including separators
+    #[must_use]
+    pub fn lane_offsets(&self, locale: &Locale) -> Vec<Metre> {
+        self.lane_widths(locale)
+            .into_iter()
+            .scan(Metre::new(0.0), |offset, width| {
+                let left = *offset;
+                *offset += width;
+                Some(left)
+            })
+            .collect()
+    }
+
+    /// Each lane with the offset of its left edge in metres from the left edge of the road,
+    /// left to right, including separators, see [`Road::lane_offsets`]
+    #[must_use]
+    pub fn lanes_with_offsets(&self, locale: &Locale) -> Vec<(Metre, &Lane)> {
+        self.lane_offsets(locale)
+            .into_iter()
+            .zip(&self.lanes)
+            .collect()
+    }
+
+    /// Index, left edge offset and width in metres of each motor or bus travel lane,
+    /// left to right, e.g. to draw stop lines and turn arrows across these lanes
+    #[must_use]
+    pub fn travel_lane_positions(&self, locale: &Locale) -> Vec<(usize, Metre, Metre)> {
         self.lanes
             .iter()
-            .map(|lane| lane.width(locale, self.highway.r#type()))
-            .sum::<Metre>()
+            .zip(self.lane_offsets(locale))
+            .zip(self.lane_widths(locale))
+            .enumerate()
+            .filter(|(_, ((lane, _), _))| {
+                matches!(
+                    lane,
+                    Lane::Travel {
+                        designated: Designated::Motor | Designated::Bus,
+                        ..
+                    }
+                )
+            })
+            .map(|(index, ((_, offset), width))| (index, offset, width))
+            .collect()
+    }
+
+    /// Side of the centre line that the lane at `index` is on, or `None` if out of range.
+    /// The centre line is midway across the motor and bus travel lanes,
+    /// or across the whole road if there are none.
+    #[must_use]
+    pub fn lane_side(&self, index: usize, locale: &Locale) -> Option<Side> {
+        let offsets = self.lane_offsets(locale);
+        let widths = self.lane_widths(locale);
+        let offset = offsets.get(index)?.val();
+        let width = widths.get(index)?.val();
+        let positions = self.travel_lane_positions(locale);
+        let (left, right) = match (positions.first(), positions.last()) {
+            (Some((_, left, _)), Some((_, offset, width))) => {
+                (left.val(), (*offset + *width).val())
+            },
+            _ => (0.0, self.width(locale).val()),
+        };
+        let centre = (left + right) / 2.0;
+        Some(if offset + width <= centre {
+            Side::Left
+        } else if offset >= centre {
+            Side::Right
+        } else {
+            Side::Centre
+        })
+    }
+
+    /// Flip left and right, reverses the order of lanes and mirrors them.
+    /// The directions of the lanes are unchanged,
+    /// so this is the road as it would be when driving on the other side, see [`Road::reverse`].
+    #[must_use]
+    pub fn mirror(self) -> Self {
+        Self {
+            lanes: self.lanes.into_iter().rev().map(Lane::mirror).collect(),
+            bus_bay: self.bus_bay.map(BusBay::mirror),
+            defaulted_widths: self.defaulted_widths.into_iter().rev().collect(),
+            ..self
+        }
+    }
+
+    /// The same road described along the reversed way.
+    /// Unlike [`Road::mirror`], the lanes also travel in the opposite direction,
+    /// so that the road is unchanged for someone standing on it.
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Self {
+            lanes: self.lanes.into_iter().rev().map(Lane::reverse).collect(),
+            bus_bay: self.bus_bay.map(BusBay::mirror),
+            oneway_conditional: self
+                .oneway_conditional
+                .as_deref()
+                .map(reverse_oneway_conditional),
+            defaulted_widths: self.defaulted_widths.into_iter().rev().collect(),
+            ..self
+        }
+    }
+
+    /// Equality where widths and speeds may differ by up to the given tolerances,
+    /// to compare computed roads. Everything else must be equal, as in `PartialEq`.
+    #[must_use]
+    pub fn approx_eq(&self, other: &Self, width_tol: Metre, speed_tol: Speed) -> bool {
+        let speed_eq = |a: &Option<Speed>, b: &Option<Speed>| match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => (a.kph() - b.kph()).abs() <= speed_tol.kph(),
+            (None, Some(_)) | (Some(_), None) => false,
+        };
+        self.highway == other.highway
+            && self.bus_bay == other.bus_bay
+            && self.surface == other.surface
+            && self.max_weight == other.max_weight
+            && speed_eq(&self.max_speed_hgv, &other.max_speed_hgv)
+            && speed_eq(&self.max_speed_bus, &other.max_speed_bus)
+            && self.track_grade == other.track_grade
+            && self.oneway_conditional == other.oneway_conditional
+            && self.layer == other.layer
+            && self.is_area == other.is_area
+            && self.lanes.len() == other.lanes.len()
+            && self
+                .lanes
+                .iter()
+                .zip(&other.lanes)
+                .all(|(lane, other)| lane.approx_eq(other, width_tol, speed_tol))
+    }
+
+    /// Merge with the road of an adjacent segment of the same way, such as after a split,
+    /// when both have the same cross-section.
+    /// The locale of the defaults is kept from `self`.
+    ///
+    /// # Errors
+    ///
+    /// If the lanes or the properties of the roads differ
+    pub fn try_merge(self, other: Self) -> Result<Self, MergeError> {
+        if self.lanes.len() != other.lanes.len() {
+            return Err(MergeError::LaneCount {
+                left: self.lanes.len(),
+                right: other.lanes.len(),
+            });
+        }
+        if let Some(index) = self
+            .lanes
+            .iter()
+            .zip(&other.lanes)
+            .position(|(lane, other)| lane != other)
+        {
+            return Err(MergeError::Lane { index });
+        }
+        if self.highway != other.highway
+            || self.bus_bay != other.bus_bay
+            || self.surface != other.surface
+            || self.max_weight != other.max_weight
+            || self.max_speed_hgv != other.max_speed_hgv
+            || self.max_speed_bus != other.max_speed_bus
+            || self.track_grade != other.track_grade
+            || self.oneway_conditional != other.oneway_conditional
+            || self.layer != other.layer
+            || self.is_area != other.is_area
+        {
+            return Err(MergeError::Road);
+        }
+        Ok(self)
+    }
+
+    /// Normalise trivial differences, so that equivalent roads compare equal:
+    /// - separators without markings are removed
+    /// - adjacent separators are merged into one, keeping the markings left to right
+    /// - lane access without any value is removed
+    /// - the only two travel lanes of a designation, side by side in opposite directions
+    ///   and otherwise the same, become a single lane in both directions of their combined width
+    #[must_use]
+    pub fn canonicalize(self) -> Self {
+        let mut lanes: Vec<Lane> = Vec::with_capacity(self.lanes.len());
+        let mut defaulted_widths: Vec<bool> = Vec::with_capacity(self.lanes.len());
+        let defaulted = self.defaulted_widths.into_iter().chain(iter::repeat(false));
+        for (lane, defaulted) in self.lanes.into_iter().zip(defaulted) {
+            if let Lane::Separator { markings } = &lane {
+                if markings.is_empty() {
+                    continue;
+                }
+            }
+            if let (Some(Lane::Separator { markings: previous }), Lane::Separator { markings }) =
+                (lanes.last_mut(), &lane)
+            {
+                previous.append(markings.clone());
+                continue;
+            }
+            lanes.push(lane.canonicalize());
+            defaulted_widths.push(defaulted);
+        }
+        let count = |designated: Designated| {
+            lanes
+                .iter()
+                .filter(
+                    |lane| matches!(lane, Lane::Travel { designated: d, .. } if *d == designated),
+                )
+                .count()
+        };
+        let merge = lanes
+            .windows(2)
+            .enumerate()
+            .find_map(|(index, pair)| match pair {
+                [left @ Lane::Travel { designated, .. }, right] if count(*designated) == 2 => {
+                    left.merge_opposing(right).map(|merged| (index, merged))
+                },
+                _ => None,
+            });
+        if let Some((index, merged)) = merge {
+            if let Some(end) = index.checked_add(2) {
+                lanes.drain(index..end);
+                lanes.insert(index, merged);
+                // The combined width is no longer a default
+                defaulted_widths.drain(index..end);
+                defaulted_widths.insert(index, false);
+            }
+        }
+        Self {
+            lanes,
+            defaulted_widths,
+            ..self
+        }
+    }
+}
+
+/// Swap `yes` and `-1` in each `<value> @ <condition>` rule of `oneway:conditional=*`,
+/// leaving the conditions, which may themselves contain `;`, untouched.
+fn reverse_oneway_conditional(conditional: &str) -> String {
+    fn reverse_value(value: &str) -> String {
+        let reversed = match value.trim() {
+            "yes" | "true" | "1" => "-1",
+            "-1" => "yes",
+            _ => return value.to_owned(),
+        };
+        value.replacen(value.trim(), reversed, 1)
+    }
+    let mut reversed = String::with_capacity(conditional.len());
+    let mut value = String::new();
+    let mut in_value = true;
+    let mut depth = 0_usize;
+    for c in conditional.chars() {
+        if in_value {
+            if c == '@' {
+                reversed.push_str(&reverse_value(&value));
+                reversed.push(c);
+                value.clear();
+                in_value = false;
+            } else {
+                value.push(c);
+            }
+            continue;
+        }
+        reversed.push(c);
+        match c {
+            '(' => depth = depth.saturating_add(1),
+            ')' => depth = depth.saturating_sub(1),
+            ';' if depth == 0 => in_value = true,
+            _ => {},
+        }
+    }
+    reversed.push_str(&value);
+    reversed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BusBay, Color, Designated, Direction, Lane, LaneOrderError, Marking, Markings, MergeError,
+        Road, Role, Side, Style, ValidationWarning,
+    };
+    use crate::locale::{DrivingSide, Locale, SeparatorWidths};
+    use crate::metric::{Metre, Speed};
+    use crate::tag::{Highway, HighwayType, Tags};
+    use crate::transform::{
+        lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig, TransformResult,
+    };
+
+    #[test]
+    fn lane_widths_sum_to_width() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "2"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let widths = road.lane_widths(&locale);
+        assert_eq!(widths.len(), road.lanes.len());
+        assert_eq!(widths.into_iter().sum::<Metre>(), road.width(&locale));
+    }
+
+    #[test]
+    fn width_invariant_under_mirror() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "4"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let mut widths = road.lane_widths(&locale);
+        let width = road.width(&locale);
+        let mirrored = road.mirror();
+        widths.reverse();
+        assert_eq!(mirrored.lane_widths(&locale), widths);
+        // summed in the opposite order
+        assert!((mirrored.width(&locale).val() - width.val()).abs() < 1e-9_f64);
+    }
+
+    #[test]
+    fn without_separators() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "2"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let with = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+            .unwrap()
+            .road;
+        let without = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        assert!(with.has_separators());
+        assert_ne!(with, without);
+        assert_eq!(with.without_separators(), without);
+    }
+
+    #[test]
+    fn separators() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[["highway", "residential"], ["lanes", "2"]]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert!(road.num_separators() > 0);
+        for (index, markings) in road.separators() {
+            assert!(matches!(
+                road.lanes.get(index),
+                Some(Lane::Separator { markings: lane }) if lane == markings
+            ));
+        }
+
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        assert_eq!(road.num_separators(), 0);
+    }
+
+    #[test]
+    fn width_follows_locale_line_width() {
+        let tags = Tags::from_str_pairs(&[["highway", "secondary"], ["lanes", "4"]]).unwrap();
+        let width = |locale: &Locale| {
+            tags_to_lanes(&tags, locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road
+                .width(locale)
+        };
+        let narrow = Locale::builder().driving_side(DrivingSide::Right).build();
+        let wide = Locale::builder()
+            .driving_side(DrivingSide::Right)
+            .separator_widths(SeparatorWidths {
+                line: Metre::new(0.5),
+                ..SeparatorWidths::default()
+            })
+            .build();
+        assert!(width(&wide).val() > width(&narrow).val());
+    }
+
+    #[test]
+    fn validate_parking_between_motor_lanes() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let motor = |direction| Lane::Travel {
+            direction: Some(direction),
+            designated: Designated::Motor,
+            width: None,
+            max_speed: None,
+            advisory_speed: None,
+            access: None,
+            conditional: None,
+            steps: None,
+            cycleway: None,
+            footway: None,
+            turn,
+        };
+        let parking = Lane::Parking {
+            direction: Direction::Forward,
+            designated: Designated::Motor,
+            width: None,
+            orientation: None,
+            conditional: None,
+            condition: None,
+            fee: None,
+        };
+        let road = |lanes| Road::new(lanes, Highway::active(HighwayType::UnknownRoad), &locale);
+        let edge = road(vec![
+            motor(Direction::Backward),
+            motor(Direction::Forward),
+            parking.clone(),
+        ])
+        .unwrap();
+        assert!(edge.validate().is_empty());
+        let between = road(vec![
+            motor(Direction::Backward),
+            parking,
+            motor(Direction::Forward),
+        ])
+        .unwrap();
+        assert_eq!(
+            between.validate(),
+            vec![ValidationWarning::ParkingBetweenMotorLanes { index: 1 }]
+        );
+    }
+
+    #[test]
+    fn recompute_defaults() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["width:lanes", "3.35|"],
+        ])
+        .unwrap();
+        let netherlands = Locale::builder()
+            .driving_side(DrivingSide::Right)
+            .iso_3166("NL")
+            .build();
+        let elsewhere = Locale::builder().driving_side(DrivingSide::Right).build();
+        let widths = |road: &Road| -> Vec<Option<Metre>> {
+            road.lanes
+                .iter()
+                .filter_map(|lane| match lane {
+                    Lane::Travel { width, .. } => Some(*width),
+                    _ => None,
+                })
+                .collect()
+        };
+        let mut road = tags_to_lanes(&tags, &netherlands, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(
+            widths(&road),
+            vec![Some(Metre::new(3.35)), Some(Metre::new(3.35))]
+        );
+        let parsed = road.clone();
+        // The tagged width is kept, even though it equals the default it was parsed under
+        road.recompute_defaults(&elsewhere);
+        assert_eq!(
+            widths(&road),
+            vec![Some(Metre::new(3.35)), Some(Metre::new(3.5))]
+        );
+        assert_eq!(road.defaults_locale(), Some(&elsewhere));
+        road.recompute_defaults(&netherlands);
+        assert_eq!(road, parsed);
+
+        // Where the defaults came from is kept when serialized
+        let mut deserialized: Road =
+            serde_json::from_str(&serde_json::to_string(&parsed).unwrap()).unwrap();
+        assert_eq!(deserialized.defaulted_widths(), parsed.defaulted_widths());
+        deserialized.recompute_defaults(&elsewhere);
+        assert_eq!(
+            widths(&deserialized),
+            vec![Some(Metre::new(3.35)), Some(Metre::new(3.5))]
+        );
+    }
+
+    #[test]
+    fn canonicalize_equivalent_tags() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = |tags: &[[&str; 2]]| {
+            tags_to_lanes(
+                &Tags::from_str_pairs(tags).unwrap(),
+                &locale,
+                &TagsToLanesConfig::default(),
+            )
+            .unwrap()
+            .road
+            .canonicalize()
+        };
+        assert_eq!(
+            road(&[["highway", "residential"], ["sidewalk", "both"]]),
+            road(&[
+                ["highway", "residential"],
+                ["sidewalk:left", "yes"],
+                ["sidewalk:right", "yes"],
+            ]),
+        );
+    }
+
+    #[test]
+    fn canonicalize_merges_separators() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[["highway", "residential"], ["lanes", "2"]]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+            .unwrap()
+            .road;
+        let line = || Marking {
+            style: Style::SolidLine,
+            width: None,
+            color: Some(Color::White),
+        };
+        let mut split = road.clone();
+        let position = split.lanes.iter().position(Lane::is_separator).unwrap();
+        split.lanes.insert(
+            position,
+            Lane::Separator {
+                markings: Markings::new(vec![]),
+            },
+        );
+        split.lanes.insert(
+            position,
+            Lane::Separator {
+                markings: Markings::new(vec![line()]),
+            },
+        );
+        let mut merged = road;
+        if let Some(Lane::Separator { markings }) = merged.lanes.get_mut(position) {
+            let mut markings_with_line = Markings::new(vec![line()]);
+            markings_with_line.append(markings.clone());
+            *markings = markings_with_line;
+        }
+        assert_ne!(split, merged);
+        assert_eq!(split.canonicalize(), merged.canonicalize());
+    }
+
+    #[test]
+    fn canonicalize_opposing_lanes() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = |tags: &[[&str; 2]]| {
+            tags_to_lanes(
+                &Tags::from_str_pairs(tags).unwrap(),
+                &locale,
+                &TagsToLanesConfig::new(false, false),
+            )
+            .unwrap()
+            .road
+        };
+        let opposing = road(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["width:lane:1", "2.5"],
+            ["width:lane:2", "2.5"],
+        ]);
+        let both = road(&[
+            ["highway", "residential"],
+            ["lanes", "1"],
+            ["width:lane:1", "5"],
+        ]);
+        assert_ne!(opposing, both);
+        let opposing = opposing.canonicalize();
+        assert_eq!(opposing, both.canonicalize());
+        assert!(matches!(
+            opposing.lanes.as_slice(),
+            [Lane::Travel {
+                direction: Some(Direction::Both),
+                ..
+            }]
+        ));
+
+        // More than two lanes are not merged
+        let four = road(&[["highway", "residential"], ["lanes", "4"]]).canonicalize();
+        assert_eq!(four.lanes.len(), 4);
+    }
+
+    #[test]
+    fn travel_lane_positions() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "4"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let offsets = road.lane_offsets(&locale);
+        let widths = road.lane_widths(&locale);
+        let positions = road.travel_lane_positions(&locale);
+        assert_eq!(positions.len(), 4);
+        for (index, offset, width) in positions {
+            assert!(matches!(road.lanes.get(index), Some(Lane::Travel { .. })));
+            assert_eq!(offsets.get(index), Some(&offset));
+            assert_eq!(widths.get(index), Some(&width));
+        }
+        // lanes are contiguous
+        for ((offset, width), next) in offsets.iter().zip(&widths).zip(offsets.iter().skip(1)) {
+            assert!(((*offset + *width).val() - next.val()).abs() < 1e-9_f64);
+        }
+    }
+
+    #[test]
+    fn lanes_with_offsets() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["width:lanes", "3|4"],
+            ["sidewalk", "right"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        let offsets: Vec<Metre> = road
+            .lanes_with_offsets(&locale)
+            .into_iter()
+            .map(|(offset, _)| offset)
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![Metre::new(0.0), Metre::new(3.0), Metre::new(7.0)]
+        );
+        assert!(road
+            .lanes_with_offsets(&locale)
+            .into_iter()
+            .zip(&road.lanes)
+            .all(|((_, lane), expected)| lane == expected));
+    }
+
+    #[test]
+    fn approx_eq_within_tolerance() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[["highway", "secondary"], ["lanes", "2"]]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let widen = |road: &Road, by: f64| Road {
+            lanes: road
+                .lanes
+                .iter()
+                .cloned()
+                .map(|lane| match lane {
+                    Lane::Travel {
+                        direction,
+                        designated,
+                        width,
+                        max_speed,
+                        advisory_speed,
+                        access,
+                        conditional,
+                        steps,
+                        cycleway,
+                        footway,
+                        turn,
+                    } => Lane::Travel {
+                        direction,
+                        designated,
+                        width: Some(width.unwrap_or(Lane::DEFAULT_WIDTH) + Metre::new(by)),
+                        max_speed,
+                        advisory_speed,
+                        access,
+                        conditional,
+                        steps,
+                        cycleway,
+                        footway,
+                        turn,
+                    },
+                    lane => lane,
+                })
+                .collect(),
+            ..road.clone()
+        };
+        let base = widen(&road, 0.0);
+        let wider = widen(&road, 0.01);
+        assert_ne!(base, wider);
+        assert!(base.approx_eq(&wider, Metre::new(0.05), Speed::Kph(1.0)));
+        assert!(!base.approx_eq(&widen(&road, 0.5), Metre::new(0.05), Speed::Kph(1.0)));
+    }
+
+    #[test]
+    fn lane_side() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "3"],
+            ["lanes:forward", "1"],
+            ["lanes:backward", "1"],
+            ["lanes:both_ways", "1"],
+            ["sidewalk", "right"],
+            ["shoulder", "no"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        assert_eq!(
+            (0..road.lanes.len())
+                .map(|index| road.lane_side(index, &locale))
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Side::Left),
+                Some(Side::Centre),
+                Some(Side::Right),
+                Some(Side::Right),
+            ]
+        );
+        assert_eq!(road.lane_side(road.lanes.len(), &locale), None);
+        // The same lanes, the other way round
+        let mirrored = road.mirror();
+        assert_eq!(mirrored.lane_side(0, &locale), Some(Side::Left));
+        assert_eq!(mirrored.lane_side(2, &locale), Some(Side::Centre));
+        assert_eq!(mirrored.lane_side(3, &locale), Some(Side::Right));
+    }
+
+    #[test]
+    fn direction_order() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "3"],
+            ["lanes:forward", "1"],
+            ["lanes:backward", "1"],
+            ["lanes:both_ways", "1"],
+            ["cycleway:right", "track"],
+            ["cycleway:right:oneway", "no"],
+        ])
+        .unwrap();
+        for driving_side in [DrivingSide::Right, DrivingSide::Left] {
+            let locale = Locale::builder().driving_side(driving_side).build();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            assert_eq!(road.check_direction_order(&locale), Ok(()));
+            let built = Road::new(road.lanes.clone(), road.highway.clone(), &locale).unwrap();
+            assert_eq!(built.lanes, road.lanes);
+            // Driving on the other side
+            let other = Locale::builder()
+                .driving_side(driving_side.opposite())
+                .build();
+            assert!(road.check_direction_order(&other).is_err());
+        }
+    }
+
+    #[test]
+    fn direction_order_invalid() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[["highway", "residential"], ["lanes", "2"]]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        // forward, backward, forward
+        let mut lanes = road.lanes.clone();
+        lanes.reverse();
+        let first = lanes.first().cloned();
+        lanes.extend(first);
+        assert_eq!(
+            Road::new(lanes, road.highway, &locale),
+            Err(LaneOrderError { index: 1 })
+        );
+    }
+
+    #[test]
+    fn mirror_and_reverse() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["sidewalk", "right"],
+            ["bus_bay", "right"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let directions = |road: &Road| -> Vec<Option<Direction>> {
+            road.lanes
+                .iter()
+                .filter_map(|lane| match lane {
+                    Lane::Travel { direction, .. } => Some(*direction),
+                    _ => None,
+                })
+                .collect()
+        };
+        // Mirrored, the lanes keep their directions and belong on the other side
+        let mirrored = road.clone().mirror();
+        assert_eq!(
+            directions(&mirrored),
+            vec![None, Some(Direction::Forward), Some(Direction::Backward)]
+        );
+        assert!(mirrored.check_direction_order(&locale).is_err());
+        let left = Locale::builder().driving_side(DrivingSide::Left).build();
+        assert_eq!(mirrored.check_direction_order(&left), Ok(()));
+        // Reversed, the lanes also change direction and stay on the same side
+        let reversed = road.clone().reverse();
+        assert_eq!(
+            directions(&reversed),
+            vec![None, Some(Direction::Backward), Some(Direction::Forward)]
+        );
+        assert_eq!(reversed.check_direction_order(&locale), Ok(()));
+        assert_eq!(reversed.bus_bay, Some(BusBay::Left));
+        assert_eq!(reversed.reverse(), road);
+    }
+
+    #[test]
+    fn reverse_oneway_conditional() {
+        let mut road = Road::from_lanes(Vec::new(), Highway::active(HighwayType::UnknownRoad));
+        road.oneway_conditional =
+            Some("-1 @ (Mo-Fr 16:00-19:00); yes @ (Sa 07:00-09:00; Su 07:00-09:00)".to_owned());
+        let reversed = road.clone().reverse();
+        assert_eq!(
+            reversed.oneway_conditional.as_deref(),
+            Some("yes @ (Mo-Fr 16:00-19:00); -1 @ (Sa 07:00-09:00; Su 07:00-09:00)")
+        );
+        assert_eq!(reversed.reverse(), road);
+
+        road.oneway_conditional = Some("no @ (Mo-Fr 16:00-19:00)".to_owned());
+        assert_eq!(road.clone().reverse(), road);
+    }
+
+    #[test]
+    fn cross_section() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "2"],
+            ["sidewalk", "right"],
+            ["shoulder", "no"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let cross_section = road.cross_section(&locale);
+        assert_eq!(cross_section.width, road.width(&locale));
+        assert_eq!(
+            cross_section
+                .slices
+                .iter()
+                .map(|slice| slice.role)
+                .collect::<Vec<_>>(),
+            vec![
+                Role::Motor,
+                Role::Separator,
+                Role::Motor,
+                Role::Separator,
+                Role::Foot,
+            ]
+        );
+        assert_eq!(
+            cross_section
+                .slices
+                .iter()
+                .map(|slice| slice.arrows)
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Direction::Backward),
+                None,
+                Some(Direction::Forward),
+                None,
+                None,
+            ]
+        );
+        assert!(cross_section
+            .slices
+            .iter()
+            .all(|slice| (slice.role == Role::Separator) != slice.markings.is_empty()));
+        // Slices are contiguous from the left edge
+        let mut offset = Metre::new(0.0);
+        for slice in &cross_section.slices {
+            assert!((slice.offset.val() - offset.val()).abs() < 1e-9_f64);
+            offset += slice.width;
+        }
+        assert!((offset.val() - cross_section.width.val()).abs() < 1e-9_f64);
+    }
+
+    #[test]
+    fn collapsed_view() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "4"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        let runs: Vec<(Option<Direction>, usize)> = road
+            .collapsed_view()
+            .into_iter()
+            .map(|(lane, count)| match lane {
+                Lane::Travel { direction, .. } => (direction, count),
+                lane => panic!("expected travel lane, got {:?}", lane),
+            })
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                (None, 1),
+                (Some(Direction::Backward), 2),
+                (Some(Direction::Forward), 2),
+                (None, 1),
+            ]
+        );
+        assert_eq!(road.lanes.len(), 6);
+
+        let with_separators = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(
+            with_separators.collapsed_view().len(),
+            with_separators.lanes.len()
+        );
+    }
+
+    #[test]
+    fn is_accessible_to() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = |tags: &[[&str; 2]]| {
+            tags_to_lanes(
+                &Tags::from_str_pairs(tags).unwrap(),
+                &locale,
+                &TagsToLanesConfig::default(),
+            )
+            .unwrap()
+            .road
+        };
+
+        let residential = road(&[["highway", "residential"]]);
+        assert!(residential.is_accessible_to(Designated::Motor));
+        assert!(residential.is_accessible_to(Designated::Bus));
+        assert!(residential.is_accessible_to(Designated::Bicycle));
+        // Walking on the carriageway, without a sidewalk
+        assert!(residential.is_accessible_to(Designated::Foot));
+        let no_foot = road(&[["highway", "residential"], ["foot", "no"]]);
+        assert!(!no_foot.is_accessible_to(Designated::Foot));
+        let no_bicycle = road(&[["highway", "residential"], ["bicycle", "no"]]);
+        assert!(!no_bicycle.is_accessible_to(Designated::Bicycle));
+        assert!(no_bicycle.is_accessible_to(Designated::Motor));
+        let no_motor_vehicle = road(&[["highway", "residential"], ["motor_vehicle", "no"]]);
+        assert!(!no_motor_vehicle.is_accessible_to(Designated::Motor));
+        assert!(no_motor_vehicle.is_accessible_to(Designated::Bicycle));
+        let no_access = road(&[["highway", "residential"], ["access", "no"]]);
+        assert!(!no_access.is_accessible_to(Designated::Motor));
+        assert!(!no_access.is_accessible_to(Designated::Bicycle));
+        assert!(!no_access.is_accessible_to(Designated::Foot));
+        let no_access_but_bicycle = road(&[
+            ["highway", "residential"],
+            ["access", "no"],
+            ["bicycle", "yes"],
+        ]);
+        assert!(!no_access_but_bicycle.is_accessible_to(Designated::Motor));
+        assert!(no_access_but_bicycle.is_accessible_to(Designated::Bicycle));
+
+        let motorway = road(&[["highway", "motorway"], ["lanes", "2"], ["oneway", "yes"]]);
+        assert!(motorway.is_accessible_to(Designated::Motor));
+        assert!(!motorway.is_accessible_to(Designated::Bicycle));
+        assert!(!motorway.is_accessible_to(Designated::Foot));
+
+        let bike_lane = road(&[
+            ["highway", "motorway"],
+            ["oneway", "yes"],
+            ["cycleway", "lane"],
+        ]);
+        assert!(bike_lane.is_accessible_to(Designated::Bicycle));
+
+        let sidewalk = road(&[["highway", "residential"], ["sidewalk", "both"]]);
+        assert!(sidewalk.is_accessible_to(Designated::Foot));
+
+        let cycleway = road(&[["highway", "cycleway"]]);
+        assert!(cycleway.is_accessible_to(Designated::Bicycle));
+        assert!(!cycleway.is_accessible_to(Designated::Motor));
+    }
+
+    #[test]
+    fn access_yes_reopens_motorway() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "motorway"],
+            ["lanes", "2"],
+            ["oneway", "yes"],
+            ["access", "yes"],
+        ])
+        .unwrap();
+        let result = TransformResult::from(
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap(),
+        );
+        // Not reported as unimplemented access
+        assert!(!result.codes.contains(&"unimplemented"));
+        let road = result.road;
+        assert!(road.is_accessible_to(Designated::Motor));
+        assert!(road.is_accessible_to(Designated::Bicycle));
+        assert!(road.is_accessible_to(Designated::Foot));
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("access"), Some("yes"));
+    }
+
+    #[test]
+    fn access_yes_is_a_fallback() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "motorway"],
+            ["lanes", "2"],
+            ["oneway", "yes"],
+            ["access", "yes"],
+            ["bicycle", "no"],
+            ["foot", "no"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        assert!(road.is_accessible_to(Designated::Motor));
+        assert!(!road.is_accessible_to(Designated::Bicycle));
+        assert!(!road.is_accessible_to(Designated::Foot));
+    }
+
+    #[test]
+    fn try_merge() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = |tags: &[[&str; 2]]| {
+            tags_to_lanes(
+                &Tags::from_str_pairs(tags).unwrap(),
+                &locale,
+                &TagsToLanesConfig::default(),
+            )
+            .unwrap()
+            .road
+        };
+        let base = [["highway", "residential"], ["lanes", "2"]];
+        let merged = road(&base).try_merge(road(&base)).unwrap();
+        assert_eq!(merged, road(&base));
+
+        assert!(matches!(
+            road(&base).try_merge(road(&[["highway", "residential"], ["lanes", "4"]])),
+            Err(MergeError::LaneCount { .. })
+        ));
+        assert!(matches!(
+            road(&base).try_merge(road(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["maxspeed", "30"],
+            ])),
+            Err(MergeError::Lane { .. })
+        ));
+        assert_eq!(
+            road(&base).try_merge(road(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["surface", "gravel"],
+            ])),
+            Err(MergeError::Road)
+        );
     }
 }
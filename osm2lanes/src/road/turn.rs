@@ -0,0 +1,154 @@
+use serde::{Deserialize, Serialize};
+
+/// Turn marking of a lane, one of the values of `turn:lanes=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:turn>
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TurnDirection {
+    /// Explicitly no marking
+    None,
+    Through,
+    Left,
+    SlightLeft,
+    SharpLeft,
+    Right,
+    SlightRight,
+    SharpRight,
+    /// U-turn
+    Reverse,
+    /// The lane ends, merge into the lane to the left
+    MergeToLeft,
+    /// The lane ends, merge into the lane to the right
+    MergeToRight,
+}
+
+impl TurnDirection {
+    /// Mirror the turn, swapping left and right
+    #[must_use]
+    pub const fn mirror(self) -> Self {
+        match self {
+            Self::Left => Self::Right,
+            Self::SlightLeft => Self::SlightRight,
+            Self::SharpLeft => Self::SharpRight,
+            Self::Right => Self::Left,
+            Self::SlightRight => Self::SlightLeft,
+            Self::SharpRight => Self::SharpLeft,
+            Self::MergeToLeft => Self::MergeToRight,
+            Self::MergeToRight => Self::MergeToLeft,
+            Self::None | Self::Through | Self::Reverse => self,
+        }
+    }
+
+    /// The `turn:lanes=*` value
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Through => "through",
+            Self::Left => "left",
+            Self::SlightLeft => "slight_left",
+            Self::SharpLeft => "sharp_left",
+            Self::Right => "right",
+            Self::SlightRight => "slight_right",
+            Self::SharpRight => "sharp_right",
+            Self::Reverse => "reverse",
+            Self::MergeToLeft => "merge_to_left",
+            Self::MergeToRight => "merge_to_right",
+        }
+    }
+
+    /// The turns of a single lane, separated by `;`, e.g. `left;through`.
+    /// An empty value, as in `turn:lanes=|through|`, is no marking, the same as `none`.
+    ///
+    /// # Errors
+    ///
+    /// The first unknown turn
+    pub fn parse_lane(value: &str) -> Result<Vec<Self>, UnknownTurn> {
+        if value.is_empty() {
+            return Ok(vec![Self::None]);
+        }
+        value.split(';').map(str::parse).collect()
+    }
+}
+
+/// A turn that is not one of the values of `turn:lanes=*`
+#[derive(Debug, PartialEq)]
+pub struct UnknownTurn(pub String);
+
+impl std::fmt::Display for UnknownTurn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown turn {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownTurn {}
+
+impl std::str::FromStr for TurnDirection {
+    type Err = UnknownTurn;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "through" => Ok(Self::Through),
+            "left" => Ok(Self::Left),
+            "slight_left" => Ok(Self::SlightLeft),
+            "sharp_left" => Ok(Self::SharpLeft),
+            "right" => Ok(Self::Right),
+            "slight_right" => Ok(Self::SlightRight),
+            "sharp_right" => Ok(Self::SharpRight),
+            "reverse" => Ok(Self::Reverse),
+            "merge_to_left" => Ok(Self::MergeToLeft),
+            "merge_to_right" => Ok(Self::MergeToRight),
+            _ => Err(UnknownTurn(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TurnDirection, UnknownTurn};
+
+    #[test]
+    fn parse_turns() {
+        assert_eq!(
+            TurnDirection::parse_lane("left;through"),
+            Ok(vec![TurnDirection::Left, TurnDirection::Through])
+        );
+        assert_eq!(
+            TurnDirection::parse_lane("through;sideways"),
+            Err(UnknownTurn(String::from("sideways")))
+        );
+        for turn in [
+            "none",
+            "through",
+            "slight_left",
+            "reverse",
+            "merge_to_right",
+        ] {
+            assert_eq!(
+                turn.parse::<TurnDirection>().map(TurnDirection::as_str),
+                Ok(turn)
+            );
+        }
+    }
+
+    #[test]
+    fn parse_uncommon_turns() {
+        assert_eq!(
+            TurnDirection::parse_lane("sharp_left;merge_to_right"),
+            Ok(vec![TurnDirection::SharpLeft, TurnDirection::MergeToRight])
+        );
+        assert_eq!(
+            "sharp_right".parse::<TurnDirection>(),
+            Ok(TurnDirection::SharpRight)
+        );
+    }
+
+    #[test]
+    fn empty_turns() {
+        assert_eq!(TurnDirection::parse_lane(""), Ok(vec![TurnDirection::None]));
+        assert_eq!(
+            TurnDirection::parse_lane(";through"),
+            Err(UnknownTurn(String::new()))
+        );
+    }
+}
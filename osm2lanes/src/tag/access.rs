@@ -13,4 +13,42 @@ pub enum Access {
     Delivery,
     Customers,
     Designated,
+    /// Cyclists must dismount and walk, `bicycle=dismount`
+    Dismount,
+}
+
+impl std::str::FromStr for Access {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "yes" => Ok(Self::Yes),
+            "no" => Ok(Self::No),
+            "private" => Ok(Self::Private),
+            "permissive" => Ok(Self::Permissive),
+            "permit" => Ok(Self::Permit),
+            "destination" => Ok(Self::Destination),
+            "delivery" => Ok(Self::Delivery),
+            "customers" => Ok(Self::Customers),
+            "designated" => Ok(Self::Designated),
+            "dismount" => Ok(Self::Dismount),
+            _ => Err(s.to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for Access {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Yes => write!(f, "yes"),
+            Self::No => write!(f, "no"),
+            Self::Private => write!(f, "private"),
+            Self::Permissive => write!(f, "permissive"),
+            Self::Permit => write!(f, "permit"),
+            Self::Destination => write!(f, "destination"),
+            Self::Delivery => write!(f, "delivery"),
+            Self::Customers => write!(f, "customers"),
+            Self::Designated => write!(f, "designated"),
+            Self::Dismount => write!(f, "dismount"),
+        }
+    }
 }
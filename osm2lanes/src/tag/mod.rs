@@ -11,11 +11,16 @@ mod key;
 pub use key::TagKey;
 
 mod osm;
-pub use osm::{Highway, HighwayImportance, HighwayType, Lifecycle, HIGHWAY, LIFECYCLE, ONEWAY};
+pub use osm::{
+    Highway, HighwayImportance, HighwayType, Lifecycle, NonTravel, HIGHWAY, LIFECYCLE, ONEWAY,
+};
 
 mod access;
 pub use access::Access;
 
+mod tracked;
+pub(crate) use tracked::TrackedTags;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DuplicateKeyError(String);
 
@@ -233,6 +238,16 @@ impl<'de> serde::de::Visitor<'de> for TagsVisitor {
     }
 }
 
+/// Tags serialize as a map, sorted by key.
+impl Serialize for Tags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.map.serialize(serializer)
+    }
+}
+
 /// Informs Serde how to deserialize Tags.
 impl<'de> Deserialize<'de> for Tags {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -323,6 +338,17 @@ impl TagTreeVal {
     }
 }
 
+pub trait TagsRead {
+    /// Get value from tags given a key
+    fn get<T: AsRef<str>>(&self, k: T) -> Option<&str>;
+}
+
+impl TagsRead for Tags {
+    fn get<T: AsRef<str>>(&self, k: T) -> Option<&str> {
+        Tags::get(self, k)
+    }
+}
+
 pub trait TagsWrite {
     ///
     /// # Errors
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::tag::{TagKey, Tags};
+use crate::tag::{TagKey, TagsRead};
 
 pub const ONEWAY: TagKey = TagKey::from("oneway");
 pub const HIGHWAY: TagKey = TagKey::from("highway");
@@ -23,6 +23,7 @@ pub enum HighwayType {
     LivingStreet,
     // Motorized
     BusGuideway,
+    Busway,
     // Non-motorized
     Bridleway,
     Corridor,
@@ -88,6 +89,7 @@ impl std::str::FromStr for HighwayType {
             "escape" => Self::NonTravel(NonTravel::Escape),
             "bridleway" => Self::Bridleway,
             "bus_guideway" => Self::BusGuideway,
+            "busway" => Self::Busway,
             "corridor" => Self::Corridor,
             "cycleway" => Self::Cycleway,
             "footway" => Self::Footway,
@@ -113,6 +115,7 @@ impl std::fmt::Display for HighwayType {
             Self::NonTravel(v) => write!(f, "{}", v),
             Self::Bridleway => write!(f, "bridleway"),
             Self::BusGuideway => write!(f, "bus_guideway"),
+            Self::Busway => write!(f, "busway"),
             Self::Corridor => write!(f, "corridor"),
             Self::Cycleway => write!(f, "cycleway"),
             Self::Footway => write!(f, "footway"),
@@ -187,7 +190,7 @@ impl Highway {
     ///
     /// If highway missing return None
     /// If highway unknown return the unknown value
-    pub fn from_tags(tags: &Tags) -> Result<Self, Option<String>> {
+    pub fn from_tags<T: TagsRead>(tags: &T) -> Result<Self, Option<String>> {
         tags.get(HIGHWAY).ok_or(None).and_then(|s| match s {
             "construction" => {
                 let highway = tags
@@ -275,7 +278,9 @@ impl Highway {
                     | HighwayType::Residential
                     | HighwayType::Service
                     | HighwayType::Unclassified
-                    | HighwayType::UnknownRoad,
+                    | HighwayType::UnknownRoad
+                    | HighwayType::Busway
+                    | HighwayType::NonTravel(NonTravel::Escape | NonTravel::Raceway),
                 lifecycle: Lifecycle::Active | Lifecycle::Construction,
             }
         )
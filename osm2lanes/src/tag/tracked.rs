@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::str::FromStr;
+
+use super::{TagKey, TagTreeVal, Tags, TagsRead};
+use crate::transform::{RoadWarnings, TagsToLanesMsg};
+
+/// A view of tags that records which of them are read, for a single transform,
+/// see [`RoadFromTags::consumed_tags`](crate::transform::RoadFromTags::consumed_tags).
+/// The tags themselves are left untouched.
+pub(crate) struct TrackedTags<'tags> {
+    tags: &'tags Tags,
+    read: RefCell<BTreeSet<String>>,
+}
+
+impl<'tags> TrackedTags<'tags> {
+    #[must_use]
+    pub fn new(tags: &'tags Tags) -> Self {
+        Self {
+            tags,
+            read: RefCell::default(),
+        }
+    }
+
+    /// Record the key as read, if it exists
+    fn record(&self, key: &str) {
+        if self.tags.map.contains_key(key) {
+            self.read.borrow_mut().insert(key.to_owned());
+        }
+    }
+
+    /// Get value from tags given a key
+    pub fn get<T: AsRef<str>>(&self, k: T) -> Option<&'tags str> {
+        self.record(k.as_ref());
+        self.tags.get(k)
+    }
+
+    /// Get the value for the given key and parse it into T.
+    /// Add a `TagsToLanesMsg::Unsupported` if parsing fails.
+    pub fn get_parsed<K: AsRef<str>, T: FromStr>(
+        &self,
+        key: K,
+        warnings: &mut RoadWarnings,
+    ) -> Option<T> {
+        self.get(&key).and_then(|val| {
+            if let Ok(n) = val.parse::<T>() {
+                Some(n)
+            } else {
+                warnings.push(TagsToLanesMsg::unsupported_tag(
+                    key.as_ref().to_owned(),
+                    val,
+                ));
+                None
+            }
+        })
+    }
+
+    /// Return if tags key has value,
+    /// return false if key does not exist.
+    #[must_use]
+    pub fn is<T: AsRef<str>>(&self, k: T, v: &str) -> bool {
+        self.get(k) == Some(v)
+    }
+
+    /// Return if tags key has any of the values,
+    /// return false if the key does not exist.
+    #[must_use]
+    pub fn is_any<T: AsRef<str>>(&self, k: T, values: &[&str]) -> bool {
+        if let Some(v) = self.get(k) {
+            values.contains(&v)
+        } else {
+            false
+        }
+    }
+
+    /// Get a subset of the tags, e.g. to report them in a warning
+    #[must_use]
+    pub fn subset<T>(&self, keys: &[T]) -> Tags
+    where
+        T: Clone + AsRef<str>,
+    {
+        for key in keys {
+            self.record(key.as_ref());
+        }
+        self.tags.subset(keys)
+    }
+
+    /// Get the tree of the tags under a key, recording every tag in it as read
+    pub fn tree_get<K: Into<TagKey>>(&self, key: K) -> Option<&'tags TagTreeVal> {
+        let key: TagKey = key.into();
+        let prefix = format!("{}:", key.as_str());
+        self.read.borrow_mut().extend(
+            self.tags
+                .map
+                .keys()
+                .filter(|k| *k == key.as_str() || k.starts_with(&prefix))
+                .cloned(),
+        );
+        self.tags.tree().get(key)
+    }
+
+    /// Keys and values of the tags nested under a key, e.g. `width:lane:1` under `width:lane`,
+    /// recording each of them as read
+    pub fn prefixed<K: Into<TagKey>>(&self, key: K) -> Vec<(&'tags str, &'tags str)> {
+        let key: TagKey = key.into();
+        let prefix = format!("{}:", key.as_str());
+        let pairs: Vec<(&'tags str, &'tags str)> = self
+            .tags
+            .map
+            .iter()
+            .filter(|(k, _v)| k.starts_with(&prefix))
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        self.read
+            .borrow_mut()
+            .extend(pairs.iter().map(|(k, _v)| (*k).to_owned()));
+        pairs
+    }
+
+    /// The subset of the tags that have been read
+    #[must_use]
+    pub fn into_read(self) -> Tags {
+        let keys: Vec<String> = self.read.into_inner().into_iter().collect();
+        self.tags.subset(&keys)
+    }
+}
+
+impl TagsRead for TrackedTags<'_> {
+    fn get<T: AsRef<str>>(&self, k: T) -> Option<&str> {
+        TrackedTags::get(self, k)
+    }
+}
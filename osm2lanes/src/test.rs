@@ -59,9 +59,8 @@ impl TestCase {
     pub fn road(&self) -> Road {
         match &self.expected {
             Expected::Road(road) => road.clone(),
-            Expected::Output(lanes) => Road {
-                highway: Highway::active(HighwayType::UnknownRoad),
-                lanes: lanes.clone(),
+            Expected::Output(lanes) => {
+                Road::from_lanes(lanes.clone(), Highway::active(HighwayType::UnknownRoad))
             },
         }
     }
@@ -143,8 +142,8 @@ mod tests {
     use crate::road::{Lane, Marking, Printable, Road};
     use crate::tag::Highway;
     use crate::transform::{
-        lanes_to_tags, tags_to_lanes, LanesToTagsConfig, RoadError, RoadFromTags, RoadWarnings,
-        TagsToLanesConfig,
+        is_recognized_tag_key, lanes_to_tags, tags_to_lanes, LanesToTagsConfig, RoadError,
+        RoadFromTags, RoadWarnings, TagsToLanesConfig,
     };
 
     static LOG_INIT: std::sync::Once = std::sync::Once::new();
@@ -159,27 +158,27 @@ mod tests {
 
     impl Road {
         /// Eq where None is treaty as always equal
-        fn approx_eq(&self, expected: &Self) -> bool {
+        fn matches_expected(&self, expected: &Self) -> bool {
             if self.lanes.len() != expected.lanes.len() {
                 return false;
             }
             self.lanes
                 .iter()
                 .zip(expected.lanes.iter())
-                .all(|(actual, expected)| actual.approx_eq(expected))
+                .all(|(actual, expected)| actual.matches_expected(expected))
         }
     }
 
     impl Lane {
         /// Eq where None is treaty as always equal
-        fn approx_eq(&self, expected: &Self) -> bool {
+        fn matches_expected(&self, expected: &Self) -> bool {
             #[allow(clippy::unnested_or_patterns)]
             match (self, expected) {
                 (Lane::Separator { markings: actual }, Lane::Separator { markings: expected }) => {
                     actual
                         .iter()
                         .zip(expected.iter())
-                        .all(|(actual, expected)| actual.approx_eq(expected))
+                        .all(|(actual, expected)| actual.matches_expected(expected))
                 },
                 (
                     Lane::Travel {
@@ -187,43 +186,75 @@ mod tests {
                         direction: actual_direction,
                         width: actual_width,
                         max_speed: actual_max_speed,
+                        advisory_speed: actual_advisory_speed,
                         access: _actual_access,
+                        conditional: actual_conditional,
+                        steps: actual_steps,
+                        cycleway: actual_cycleway,
+                        footway: actual_footway,
+                        turn: actual_turn,
                     },
                     Lane::Travel {
                         designated: expected_designated,
                         direction: expected_direction,
                         width: expected_width,
                         max_speed: expected_max_speed,
+                        advisory_speed: expected_advisory_speed,
                         access: _expected_access,
+                        conditional: expected_conditional,
+                        steps: expected_steps,
+                        cycleway: expected_cycleway,
+                        footway: expected_footway,
+                        turn: expected_turn,
                     },
                 ) => {
                     actual_designated == expected_designated
                         && actual_direction == expected_direction
                         && approx_eq(actual_width, expected_width)
                         && approx_eq(actual_max_speed, expected_max_speed)
+                        && approx_eq(actual_advisory_speed, expected_advisory_speed)
+                        && approx_eq(actual_conditional, expected_conditional)
+                        && approx_eq(actual_steps, expected_steps)
+                        && approx_eq(actual_cycleway, expected_cycleway)
+                        && approx_eq(actual_footway, expected_footway)
+                        && approx_eq(actual_turn, expected_turn)
                 },
                 (
                     Lane::Parking {
                         designated: actual_designated,
                         direction: actual_direction,
                         width: actual_width,
+                        orientation: actual_orientation,
+                        conditional: actual_conditional,
+                        condition: actual_condition,
+                        fee: actual_fee,
                     },
                     Lane::Parking {
                         designated: expected_designated,
                         direction: expected_direction,
                         width: expected_width,
+                        orientation: expected_orientation,
+                        conditional: expected_conditional,
+                        condition: expected_condition,
+                        fee: expected_fee,
                     },
                 ) => {
                     actual_designated == expected_designated
                         && actual_direction == expected_direction
                         && approx_eq(actual_width, expected_width)
+                        && approx_eq(actual_orientation, expected_orientation)
+                        && approx_eq(actual_conditional, expected_conditional)
+                        && approx_eq(actual_condition, expected_condition)
+                        && approx_eq(actual_fee, expected_fee)
                 },
                 (
                     Lane::Shoulder {
                         width: actual_width,
+                        ..
                     },
                     Lane::Shoulder {
                         width: expected_width,
+                        ..
                     },
                 ) => approx_eq(actual_width, expected_width),
                 (actual, expected) => actual == expected,
@@ -234,7 +265,7 @@ mod tests {
     impl Marking {
         /// Eq where None is treaty as always equal
         #[allow(clippy::unnested_or_patterns)]
-        fn approx_eq(&self, expected: &Self) -> bool {
+        fn matches_expected(&self, expected: &Self) -> bool {
             self.style == expected.style
                 && approx_eq(&self.color, &expected.color)
                 && approx_eq(&self.width, &expected.width)
@@ -286,33 +317,18 @@ mod tests {
         }
 
         fn expected_road(&self) -> Road {
-            Road {
-                lanes: self
-                    .lanes()
-                    .iter()
-                    .filter(|lane| self.is_lane_enabled(lane))
-                    .cloned()
-                    .collect(),
-                highway: Highway::from_tags(&self.tags).unwrap(),
-            }
+            let mut road = self.road();
+            road.retain_lanes(|lane| self.is_lane_enabled(lane));
+            road.highway = Highway::from_tags(&self.tags).unwrap();
+            road
         }
     }
 
     impl RoadFromTags {
         /// Return a Road based upon a `RoadFromTags` with irrelevant parts filtered out.
-        fn into_filtered_road(self, test: &TestCase) -> (Road, RoadWarnings) {
-            (
-                Road {
-                    lanes: self
-                        .road
-                        .lanes
-                        .into_iter()
-                        .filter(|lane| test.is_lane_enabled(lane))
-                        .collect(),
-                    highway: self.road.highway,
-                },
-                self.warnings,
-            )
+        fn into_filtered_road(mut self, test: &TestCase) -> (Road, RoadWarnings) {
+            self.road.retain_lanes(|lane| test.is_lane_enabled(lane));
+            (self.road, self.warnings)
         }
     }
 
@@ -421,7 +437,7 @@ mod tests {
             match road_from_tags {
                 Ok(road_from_tags) => {
                     let (actual_road, warnings) = road_from_tags.into_filtered_road(test);
-                    if actual_road.approx_eq(&expected_road) {
+                    if actual_road.matches_expected(&expected_road) {
                         if test.test_has_warnings() && warnings.is_empty() {
                             test.print();
                             println!("Expected warnings. Try removing `expect_warnings`.");
@@ -470,6 +486,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_recognized_tag_keys() {
+        let tests = get_tests();
+
+        for test in &tests {
+            let locale = Locale::builder()
+                .driving_side(test.driving_side)
+                .iso_3166_option(test.iso_3166_2.as_deref())
+                .build();
+            if let Ok(road_from_tags) =
+                tags_to_lanes(&test.tags, &locale, &TagsToLanesConfig::default())
+            {
+                for [key, _value] in road_from_tags.consumed_tags().to_str_pairs() {
+                    assert!(
+                        is_recognized_tag_key(key),
+                        "{} is read but not recognized: {}",
+                        key,
+                        test
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_roundtrip() {
         env_logger_init();
@@ -501,7 +541,7 @@ mod tests {
             )
             .unwrap();
             let (output_road, _warnings) = output_lanes.into_filtered_road(test);
-            if !output_road.approx_eq(&input_road) {
+            if !output_road.matches_expected(&input_road) {
                 test.print();
                 println!("From:");
                 println!("    {}", stringify_lane_types(&input_road));
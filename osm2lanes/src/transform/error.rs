@@ -2,7 +2,7 @@ use serde::Serialize;
 
 use super::TagsToLanesMsg;
 use crate::road::Road;
-use crate::tag::DuplicateKeyError;
+use crate::tag::{DuplicateKeyError, Tags};
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct RoadWarnings(Vec<TagsToLanesMsg>);
@@ -21,6 +21,10 @@ impl RoadWarnings {
     pub fn push(&mut self, msg: TagsToLanesMsg) {
         self.0.push(msg);
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = &TagsToLanesMsg> {
+        self.0.iter()
+    }
 }
 
 impl std::fmt::Display for RoadWarnings {
@@ -41,9 +45,15 @@ impl std::fmt::Display for RoadWarnings {
 /// ```
 /// use osm2lanes::transform::{TagsToLanesMsg, RoadError};
 /// let msg: TagsToLanesMsg = TagsToLanesMsg::deprecated_tag("foo", "bar");
-/// assert_eq!("\"deprecated: 'foo=bar' - src/transform/error.rs:5:27\"", serde_json::to_string(&msg).unwrap());
+/// assert_eq!(
+///     "{\"code\":\"deprecated\",\"tags\":{\"foo\":\"bar\"},\"message\":\"deprecated: 'foo=bar' - src/transform/error.rs:5:27\"}",
+///     serde_json::to_string(&msg).unwrap(),
+/// );
 /// let err: RoadError = msg.into();
-/// assert_eq!("{\"error\":\"deprecated: 'foo=bar' - src/transform/error.rs:5:27\"}", serde_json::to_string(&err).unwrap());
+/// assert_eq!(
+///     "{\"error\":{\"code\":\"deprecated\",\"tags\":{\"foo\":\"bar\"},\"message\":\"deprecated: 'foo=bar' - src/transform/error.rs:5:27\"}}",
+///     serde_json::to_string(&err).unwrap(),
+/// );
 /// ```
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, Serialize)]
@@ -91,4 +101,42 @@ impl From<RoadWarnings> for RoadError {
 pub struct RoadFromTags {
     pub road: Road,
     pub warnings: RoadWarnings,
+    #[serde(skip)]
+    pub(crate) consumed: Tags,
+}
+
+impl RoadFromTags {
+    /// The tags that were read to build the lanes,
+    /// e.g. for an editor to show which tags were understood.
+    /// Tags that were read but warned about are included.
+    #[must_use]
+    pub fn consumed_tags(&self) -> Tags {
+        self.consumed.clone()
+    }
+}
+
+/// The road and its warnings as one object,
+/// so that a consumer such as a web worker deserializes it at once
+#[derive(Clone, Debug, Serialize)]
+pub struct TransformResult {
+    pub road: Road,
+    pub warnings: RoadWarnings,
+    /// The code of each warning, see [`TagsToLanesMsg::code`]
+    pub codes: Vec<&'static str>,
+}
+
+impl From<RoadFromTags> for TransformResult {
+    fn from(road_from_tags: RoadFromTags) -> Self {
+        let codes = road_from_tags
+            .warnings
+            .0
+            .iter()
+            .map(TagsToLanesMsg::code)
+            .collect();
+        Self {
+            road: road_from_tags.road,
+            warnings: road_from_tags.warnings,
+            codes,
+        }
+    }
 }
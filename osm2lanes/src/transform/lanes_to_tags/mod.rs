@@ -6,8 +6,11 @@ pub use self::error::LanesToTagsMsg;
 use super::{tags_to_lanes, TagsToLanesConfig};
 use crate::locale::Locale;
 use crate::metric::Speed;
-use crate::road::{Designated, Direction, Lane, Road};
-use crate::tag::{Tags, TagsWrite};
+use crate::road::{
+    Access as LaneAccess, BusBay, Cycleway, Designated, Direction, Lane, Marking,
+    ParkingOrientation, Road, Style, TurnDirection,
+};
+use crate::tag::{Access as AccessValue, HighwayType, Tags, TagsWrite};
 
 #[non_exhaustive]
 pub struct Config {
@@ -139,7 +142,29 @@ pub fn lanes_to_tags(
         .iter()
         .any(|lane| lane.is_motor() || lane.is_bus())
     {
-        tags.checked_insert("highway", "path")?;
+        let footway = road.lanes.iter().find_map(|lane| match lane {
+            Lane::Travel { footway, .. } => *footway,
+            _ => None,
+        });
+        if road.is_area {
+            tags.checked_insert("highway", "pedestrian")?;
+            tags.checked_insert("area", "yes")?;
+        } else if let Some(footway) = footway {
+            tags.checked_insert("highway", "footway")?;
+            tags.checked_insert("footway", footway.as_str())?;
+        } else {
+            tags.checked_insert("highway", "path")?;
+        }
+        let dismount = road.lanes.iter().any(|lane| match lane {
+            Lane::Travel {
+                access: Some(access),
+                ..
+            } => access.bicycle == Some(AccessValue::Dismount),
+            _ => false,
+        });
+        if dismount {
+            tags.checked_insert("bicycle", "dismount")?;
+        }
         return Ok(tags);
     }
 
@@ -155,14 +180,44 @@ pub fn lanes_to_tags(
 
     let lane_count = set_lanes(lanes, &mut tags)?;
     let oneway = set_oneway(lanes, &mut tags, locale, lane_count)?;
+    set_direction_lanes(lanes, &mut tags, oneway)?;
 
     set_shoulder(lanes, &mut tags)?;
     set_pedestrian(lanes, &mut tags)?;
     set_parking(lanes, &mut tags)?;
     set_cycleway(lanes, &mut tags, oneway)?;
-    set_busway(lanes, &mut tags, oneway)?;
+    if road.highway.r#type() != HighwayType::Busway {
+        set_busway(lanes, &mut tags, oneway)?;
+    }
+    set_bus_bay(road, &mut tags)?;
+    set_turn_lanes(lanes, &mut tags, oneway)?;
+    set_rail(lanes, &mut tags)?;
+    set_access(lanes, &mut tags)?;
+    if let Some(surface) = &road.surface {
+        tags.checked_insert("surface", surface.clone())?;
+    }
+    set_hgv_lanes(lanes, &mut tags)?;
+    if let Some(max_weight) = road.max_weight {
+        tags.checked_insert("maxweight", max_weight.to_string())?;
+    }
+    if let Some(speed) = road.max_speed_hgv {
+        tags.checked_insert("maxspeed:hgv", speed.to_tag_value(locale))?;
+    }
+    if let Some(speed) = road.max_speed_bus {
+        tags.checked_insert("maxspeed:bus", speed.to_tag_value(locale))?;
+    }
+    if let Some(grade) = road.track_grade {
+        tags.checked_insert("tracktype", format!("grade{}", grade))?;
+    }
+    if let Some(conditional) = &road.oneway_conditional {
+        tags.checked_insert("oneway:conditional", conditional.clone())?;
+    }
+    if road.layer != 0 {
+        tags.checked_insert("layer", road.layer.to_string())?;
+    }
 
-    let max_speed = get_max_speed(lanes, &mut tags)?;
+    let max_speed = get_max_speed(lanes, &mut tags, oneway, locale)?;
+    set_advisory_speed(lanes, &mut tags, locale)?;
 
     locale_additions(max_speed, locale, &mut tags)?;
 
@@ -188,22 +243,45 @@ fn set_lanes(lanes: &[Lane], tags: &mut Tags) -> Result<usize, LanesToTagsMsg> {
     Ok(lane_count)
 }
 
-/// Returns oneway
-fn set_oneway(
-    lanes: &[Lane],
-    tags: &mut Tags,
-    locale: &Locale,
-    lane_count: usize,
-) -> Result<bool, LanesToTagsMsg> {
-    if lanes.iter().filter(|lane| lane.is_motor()).all(|lane| {
+/// Tram tracks in the carriageway, see [`Designated::Rail`]
+fn set_rail(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+    if lanes.iter().any(|lane| {
         matches!(
             lane,
             Lane::Travel {
-                direction: Some(Direction::Forward),
+                designated: Designated::Rail,
                 ..
             }
         )
     }) {
+        tags.checked_insert("railway", "tram")?;
+        tags.checked_insert("tram", "yes")?;
+    }
+    Ok(())
+}
+
+/// Returns oneway
+fn set_oneway(
+    lanes: &[Lane],
+    tags: &mut Tags,
+    locale: &Locale,
+    lane_count: usize,
+) -> Result<bool, LanesToTagsMsg> {
+    // Only bus lanes, e.g. highway=busway
+    let only_bus = !lanes.iter().any(Lane::is_motor);
+    if lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || (only_bus && lane.is_bus()))
+        .all(|lane| {
+            matches!(
+                lane,
+                Lane::Travel {
+                    direction: Some(Direction::Forward),
+                    ..
+                }
+            )
+        })
+    {
         tags.checked_insert("oneway", "yes")?;
         Ok(true)
     } else {
@@ -260,10 +338,45 @@ fn set_oneway(
     }
 }
 
+/// The direction of each carriageway lane of a two way road, left to right
+fn set_direction_lanes(
+    lanes: &[Lane],
+    tags: &mut Tags,
+    oneway: bool,
+) -> Result<(), LanesToTagsMsg> {
+    if oneway {
+        return Ok(());
+    }
+    let directions: Option<Vec<&str>> = lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                direction,
+                ..
+            } => Some(direction.map(|direction| match direction {
+                Direction::Forward => "forward",
+                Direction::Backward => "backward",
+                Direction::Both => "both",
+            })),
+            _ => None,
+        })
+        .collect();
+    if let Some(directions) = directions {
+        tags.checked_insert("direction:lanes", directions.join("|"))?;
+    }
+    Ok(())
+}
+
 fn set_shoulder(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+    // The shoulder may be inside of a sidewalk
+    let mut inside_sidewalks = lanes
+        .iter()
+        .filter(|lane| !lane.is_separator() && !lane.is_foot());
+    let is_shoulder = |lane: Option<&Lane>| lane.map_or(false, Lane::is_shoulder);
     match (
-        lanes.first().unwrap().is_shoulder(),
-        lanes.last().unwrap().is_shoulder(),
+        is_shoulder(inside_sidewalks.next()),
+        is_shoulder(inside_sidewalks.next_back()),
     ) {
         (false, false) => {
             // TODO do we want to always be explicit about this?
@@ -297,41 +410,159 @@ fn set_pedestrian(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg>
 }
 
 fn set_parking(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+    let orientation = |lane: &Lane| match lane {
+        Lane::Parking { orientation, .. } => {
+            Some(match orientation.unwrap_or(ParkingOrientation::Parallel) {
+                ParkingOrientation::Parallel => "parallel",
+                ParkingOrientation::Diagonal => "diagonal",
+                ParkingOrientation::Perpendicular => "perpendicular",
+            })
+        },
+        _ => None,
+    };
     match (
         lanes
             .iter()
             .take_while(|lane| !lane.is_motor())
-            .any(|lane| matches!(lane, Lane::Parking { .. })),
+            .find_map(orientation),
         lanes
             .iter()
             .skip_while(|lane| !lane.is_motor())
-            .any(|lane| matches!(lane, Lane::Parking { .. })),
+            .find_map(orientation),
     ) {
-        (false, false) => {},
-        (true, false) => tags.checked_insert("parking:lane:left", "parallel")?,
-        (false, true) => tags.checked_insert("parking:lane:right", "parallel")?,
-        (true, true) => tags.checked_insert("parking:lane:both", "parallel")?,
+        (None, None) => {},
+        (Some(left), None) => tags.checked_insert("parking:lane:left", left)?,
+        (None, Some(right)) => tags.checked_insert("parking:lane:right", right)?,
+        (Some(left), Some(right)) if left == right => {
+            tags.checked_insert("parking:lane:both", left)?;
+        },
+        (Some(left), Some(right)) => {
+            tags.checked_insert("parking:lane:left", left)?;
+            tags.checked_insert("parking:lane:right", right)?;
+        },
+    }
+    let conditional = |lane: &Lane| match lane {
+        Lane::Parking {
+            conditional: Some(conditional),
+            ..
+        } => Some(conditional.clone()),
+        _ => None,
+    };
+    if let Some(left) = lanes
+        .iter()
+        .take_while(|lane| !lane.is_motor())
+        .find_map(conditional)
+    {
+        tags.checked_insert("parking:lane:left:conditional", left)?;
+    }
+    if let Some(right) = lanes
+        .iter()
+        .skip_while(|lane| !lane.is_motor())
+        .find_map(conditional)
+    {
+        tags.checked_insert("parking:lane:right:conditional", right)?;
+    }
+    let condition = |lane: &Lane| match lane {
+        Lane::Parking {
+            condition: Some(condition),
+            ..
+        } => Some(condition.clone()),
+        _ => None,
+    };
+    if let Some(left) = lanes
+        .iter()
+        .take_while(|lane| !lane.is_motor())
+        .find_map(condition)
+    {
+        tags.checked_insert("parking:condition:left", left)?;
+    }
+    if let Some(right) = lanes
+        .iter()
+        .skip_while(|lane| !lane.is_motor())
+        .find_map(condition)
+    {
+        tags.checked_insert("parking:condition:right", right)?;
+    }
+    let fee = |lane: &Lane| match lane {
+        Lane::Parking { fee: Some(fee), .. } => Some(if *fee { "yes" } else { "no" }),
+        _ => None,
+    };
+    if let Some(left) = lanes
+        .iter()
+        .take_while(|lane| !lane.is_motor())
+        .find_map(fee)
+    {
+        tags.checked_insert("parking:left:fee", left)?;
+    }
+    if let Some(right) = lanes
+        .iter()
+        .skip_while(|lane| !lane.is_motor())
+        .find_map(fee)
+    {
+        tags.checked_insert("parking:right:fee", right)?;
     }
     Ok(())
 }
 
 fn set_cycleway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), LanesToTagsMsg> {
-    let left_cycle_lane: Option<Direction> = lanes
+    let left_cycle: Option<&Lane> = lanes
         .iter()
         .take_while(|lane| !lane.is_motor())
-        .find(|lane| lane.is_bicycle())
-        .and_then(Lane::direction);
-    let right_cycle_lane: Option<Direction> = lanes
+        .find(|lane| lane.is_bicycle());
+    let right_cycle: Option<&Lane> = lanes
         .iter()
         .rev()
         .take_while(|lane| !lane.is_motor())
-        .find(|lane| lane.is_bicycle())
-        .and_then(Lane::direction);
-    match (left_cycle_lane.is_some(), right_cycle_lane.is_some()) {
-        (false, false) => {},
-        (true, false) => tags.checked_insert("cycleway:left", "lane")?,
-        (false, true) => tags.checked_insert("cycleway:right", "lane")?,
-        (true, true) => tags.checked_insert("cycleway:both", "lane")?,
+        .find(|lane| lane.is_bicycle());
+    let left_cycle_lane: Option<Direction> = left_cycle.and_then(Lane::direction);
+    let right_cycle_lane: Option<Direction> = right_cycle.and_then(Lane::direction);
+    // painted unless known to be a track
+    let variant = |lane: &Lane| match lane {
+        Lane::Travel {
+            cycleway: Some(cycleway),
+            ..
+        } => cycleway.as_str(),
+        _ => Cycleway::Lane.as_str(),
+    };
+    match (
+        left_cycle_lane.and(left_cycle).map(variant),
+        right_cycle_lane.and(right_cycle).map(variant),
+    ) {
+        (None, None) => {},
+        (Some(left), None) => tags.checked_insert("cycleway:left", left)?,
+        (None, Some(right)) => tags.checked_insert("cycleway:right", right)?,
+        (Some(left), Some(right)) if left == right => {
+            tags.checked_insert("cycleway:both", left)?;
+        },
+        (Some(left), Some(right)) => {
+            tags.checked_insert("cycleway:left", left)?;
+            tags.checked_insert("cycleway:right", right)?;
+        },
+    }
+
+    // advisory or exclusive, from the separator inside of the cycle lane
+    let left_separator = lanes
+        .iter()
+        .take_while(|lane| !lane.is_motor())
+        .skip_while(|lane| !lane.is_bicycle())
+        .nth(1);
+    let right_separator = lanes
+        .iter()
+        .rev()
+        .take_while(|lane| !lane.is_motor())
+        .skip_while(|lane| !lane.is_bicycle())
+        .nth(1);
+    if let Some(value) = left_separator.and_then(cycleway_lane) {
+        tags.checked_insert("cycleway:left:lane", value)?;
+    }
+    if let Some(value) = right_separator.and_then(cycleway_lane) {
+        tags.checked_insert("cycleway:right:lane", value)?;
+    }
+    if let Some(value) = left_separator.and_then(cycleway_separation) {
+        tags.checked_insert("cycleway:left:separation", value)?;
+    }
+    if let Some(value) = right_separator.and_then(cycleway_separation) {
+        tags.checked_insert("cycleway:right:separation", value)?;
     }
 
     // if the way has oneway=yes and you are allowed to cycle against that oneway flow
@@ -371,6 +602,35 @@ fn set_cycleway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), Lan
     Ok(())
 }
 
+/// `cycleway:<side>:lane=*` value for the separator inside of a cycle lane.
+/// Only `advisory` is written, as a solid line is also inferred without `exclusive`.
+fn cycleway_lane(separator: &Lane) -> Option<&'static str> {
+    match separator {
+        Lane::Separator { markings } => match markings.as_slice() {
+            [Marking {
+                style: Style::DashedLine,
+                ..
+            }] => Some("advisory"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `cycleway:<side>:separation=*` value for the separator inside of a cycle lane
+fn cycleway_separation(separator: &Lane) -> Option<&'static str> {
+    match separator {
+        Lane::Separator { markings } => match markings.as_slice() {
+            [Marking {
+                style: Style::KerbUp,
+                ..
+            }] => Some("kerb"),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn set_busway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), LanesToTagsMsg> {
     let left_bus_lane = lanes
         .iter()
@@ -409,31 +669,242 @@ fn set_busway(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), Lanes
     Ok(())
 }
 
-fn get_max_speed(lanes: &[Lane], tags: &mut Tags) -> Result<Option<Speed>, LanesToTagsMsg> {
-    let max_speeds: Vec<Speed> = lanes
+/// `turn:lanes=*` on oneway roads, otherwise `turn:lanes:{forward,backward}=*`
+fn set_turn_lanes(lanes: &[Lane], tags: &mut Tags, oneway: bool) -> Result<(), LanesToTagsMsg> {
+    let forward = turn_lanes(lanes.iter(), Direction::Forward);
+    // In their direction of travel, so right to left
+    let backward = turn_lanes(lanes.iter().rev(), Direction::Backward);
+    let keys = if oneway {
+        vec![("turn:lanes", forward)]
+    } else {
+        vec![
+            ("turn:lanes:forward", forward),
+            ("turn:lanes:backward", backward),
+        ]
+    };
+    for (key, turns) in keys {
+        if turns.iter().any(|turn| !turn.is_empty()) {
+            tags.checked_insert(key, turns.join("|"))?;
+        }
+    }
+    Ok(())
+}
+
+/// The turns of each motor and bus lane in the direction, empty without markings
+fn turn_lanes<'a>(lanes: impl Iterator<Item = &'a Lane>, wanted: Direction) -> Vec<String> {
+    lanes
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                direction: Some(direction),
+                turn,
+                ..
+            } if *direction == wanted => Some(turn),
+            _ => None,
+        })
+        .map(|turn| {
+            turn.iter()
+                .flatten()
+                .copied()
+                .map(TurnDirection::as_str)
+                .collect::<Vec<_>>()
+                .join(";")
+        })
+        .collect()
+}
+
+fn set_hgv_lanes(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+    let hgv: Vec<&str> = lanes
         .iter()
         .filter_map(|lane| match lane {
-            Lane::Travel { max_speed, .. } => *max_speed,
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                access,
+                ..
+            } => Some(
+                match access.as_ref().and_then(|access| access.hgv.as_ref()) {
+                    Some(AccessValue::No) => "no",
+                    Some(AccessValue::Yes) => "yes",
+                    Some(AccessValue::Designated) => "designated",
+                    _ => "",
+                },
+            ),
             _ => None,
         })
         .collect();
-    if let Some(max_speed) = max_speeds.first() {
-        // Check if all are the same
-        // See benches/benchmark_all_same.rs
-        if max_speeds.windows(2).all(|w| {
-            let speeds: &[Speed; 2] = w.try_into().unwrap();
-            speeds[0] == speeds[1]
-        }) {
-            tags.checked_insert("maxspeed", max_speed.to_string())?;
-            Ok(Some(*max_speed))
-        } else {
-            Err(LanesToTagsMsg::unimplemented(
-                "different max speeds per lane",
-            ))
+    if hgv.iter().any(|value| !value.is_empty()) {
+        tags.checked_insert("hgv:lanes", hgv.join("|"))?;
+    }
+    Ok(())
+}
+
+fn set_bus_bay(road: &Road, tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+    match road.bus_bay {
+        None => {},
+        Some(BusBay::Left) => tags.checked_insert("bus_bay", "left")?,
+        Some(BusBay::Right) => tags.checked_insert("bus_bay", "right")?,
+        Some(BusBay::Both) => tags.checked_insert("bus_bay", "both")?,
+    }
+    Ok(())
+}
+
+/// Access shared by all motor lanes, which is tagged on the way.
+/// Buses only get it from `access=*`, and the other modes also default to it.
+fn set_access(lanes: &[Lane], tags: &mut Tags) -> Result<(), LanesToTagsMsg> {
+    let access = common_access(lanes, |access| access.bus.as_ref());
+    if let Some(access) = &access {
+        tags.checked_insert("access", access.to_string())?;
+    }
+    for (key, value) in [
+        ("foot", common_access(lanes, |access| access.foot.as_ref())),
+        (
+            "bicycle",
+            common_access(lanes, |access| access.bicycle.as_ref()),
+        ),
+        (
+            "motor_vehicle",
+            common_access(lanes, |access| access.motor.as_ref()),
+        ),
+    ] {
+        if let Some(value) = value {
+            if access.as_ref() != Some(&value) {
+                tags.checked_insert(key, value.to_string())?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The access of a mode, if the same on all motor lanes
+fn common_access(
+    lanes: &[Lane],
+    mode: impl Fn(&LaneAccess) -> Option<&AccessValue>,
+) -> Option<AccessValue> {
+    let mut values = lanes.iter().filter_map(|lane| match lane {
+        Lane::Travel {
+            designated: Designated::Motor,
+            access,
+            ..
+        } => Some(access.as_ref().and_then(&mode)),
+        _ => None,
+    });
+    let first = values.next()??;
+    values
+        .all(|value| value == Some(first))
+        .then(|| first.clone())
+}
+
+/// `maxspeed=*` for the fastest motor or bus lane, and when the lanes differ,
+/// `maxspeed:lanes=*` on oneway roads, otherwise `maxspeed:lanes:{forward,backward}=*`,
+/// unless a lane for both directions has its own speed limit.
+fn get_max_speed(
+    lanes: &[Lane],
+    tags: &mut Tags,
+    oneway: bool,
+    locale: &Locale,
+) -> Result<Option<Speed>, LanesToTagsMsg> {
+    let carriageway: Vec<(Option<Direction>, Option<Speed>)> = lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                designated: Designated::Motor | Designated::Bus,
+                direction,
+                max_speed,
+                ..
+            } => Some((*direction, *max_speed)),
+            _ => None,
+        })
+        .collect();
+    let max_speed = match carriageway
+        .iter()
+        .filter_map(|(_direction, speed)| *speed)
+        .reduce(|max, speed| if speed > max { speed } else { max })
+    {
+        Some(max_speed) => max_speed,
+        None => return Ok(None),
+    };
+    tags.checked_insert("maxspeed", max_speed.to_tag_value(locale))?;
+    if carriageway
+        .iter()
+        .all(|(_direction, speed)| *speed == Some(max_speed))
+    {
+        return Ok(Some(max_speed));
+    }
+
+    let value = |speeds: Vec<Option<Speed>>| {
+        speeds
+            .into_iter()
+            .map(|speed| {
+                speed.map_or_else(|| "none".to_owned(), |speed| speed.to_tag_value(locale))
+            })
+            .collect::<Vec<_>>()
+            .join("|")
+    };
+    let directional = !oneway
+        && carriageway
+            .iter()
+            .all(|(direction, speed)| match direction {
+                Some(Direction::Forward | Direction::Backward) => true,
+                Some(Direction::Both) => *speed == Some(max_speed),
+                None => false,
+            });
+    if directional {
+        let forward: Vec<Option<Speed>> = carriageway
+            .iter()
+            .filter(|(direction, _speed)| *direction == Some(Direction::Forward))
+            .map(|(_direction, speed)| *speed)
+            .collect();
+        // In their direction of travel, so right to left
+        let backward: Vec<Option<Speed>> = carriageway
+            .iter()
+            .rev()
+            .filter(|(direction, _speed)| *direction == Some(Direction::Backward))
+            .map(|(_direction, speed)| *speed)
+            .collect();
+        for (key, speeds) in [
+            ("maxspeed:lanes:forward", forward),
+            ("maxspeed:lanes:backward", backward),
+        ] {
+            if speeds.iter().any(|speed| *speed != Some(max_speed)) {
+                tags.checked_insert(key, value(speeds))?;
+            }
         }
     } else {
-        Ok(None)
+        tags.checked_insert(
+            "maxspeed:lanes",
+            value(
+                carriageway
+                    .into_iter()
+                    .map(|(_direction, speed)| speed)
+                    .collect(),
+            ),
+        )?;
     }
+    Ok(Some(max_speed))
+}
+
+fn set_advisory_speed(
+    lanes: &[Lane],
+    tags: &mut Tags,
+    locale: &Locale,
+) -> Result<(), LanesToTagsMsg> {
+    let advisory_speeds: Vec<Speed> = lanes
+        .iter()
+        .filter_map(|lane| match lane {
+            Lane::Travel { advisory_speed, .. } => *advisory_speed,
+            _ => None,
+        })
+        .collect();
+    if let Some(advisory_speed) = advisory_speeds.first() {
+        if advisory_speeds.iter().all(|speed| speed == advisory_speed) {
+            tags.checked_insert("maxspeed:advisory", advisory_speed.to_tag_value(locale))?;
+        } else {
+            return Err(LanesToTagsMsg::unimplemented(
+                "different advisory speeds per lane",
+            ));
+        }
+    }
+    Ok(())
 }
 
 fn locale_additions(
@@ -468,3 +939,30 @@ fn check_roundtrip(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{lanes_to_tags, Config};
+    use crate::locale::{DrivingSide, Locale};
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn left_hand_traffic_sides() {
+        let locale = Locale::builder().driving_side(DrivingSide::Left).build();
+        for [key, value] in [
+            ["sidewalk", "left"],
+            ["sidewalk", "right"],
+            ["parking:lane:left", "parallel"],
+            ["parking:lane:right", "parallel"],
+            ["cycleway:left", "lane"],
+        ] {
+            let tags = Tags::from_str_pairs(&[["highway", "residential"], [key, value]]).unwrap();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            let roundtrip = lanes_to_tags(&road, &locale, &Config::default()).unwrap();
+            assert_eq!(roundtrip.get(key), Some(value), "{}={}", key, value);
+        }
+    }
+}
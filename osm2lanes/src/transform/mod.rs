@@ -3,10 +3,15 @@ use crate::road::{Designated, Direction, Lane};
 use crate::tag::TagKey;
 
 mod error;
-pub use error::{RoadError, RoadFromTags, RoadWarnings};
+pub use error::{RoadError, RoadFromTags, RoadWarnings, TransformResult};
 
 mod tags_to_lanes;
-pub use tags_to_lanes::{tags_to_lanes, Config as TagsToLanesConfig, Infer, TagsToLanesMsg};
+#[cfg(feature = "json")]
+pub use tags_to_lanes::tags_to_lanes_json;
+pub use tags_to_lanes::{
+    is_recognized_tag_key, recognized_tag_keys, tags_to_lanes, Config as TagsToLanesConfig, Infer,
+    TagsToLanesMsg,
+};
 
 mod lanes_to_tags;
 pub use lanes_to_tags::{lanes_to_tags, Config as LanesToTagsConfig, LanesToTagsMsg};
@@ -124,3 +129,18 @@ impl Lane {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_recognized_tag_key, recognized_tag_keys};
+
+    #[test]
+    fn recognized_keys() {
+        assert!(recognized_tag_keys().any(|key| key == "lanes"));
+        assert!(is_recognized_tag_key("cycleway:right"));
+        assert!(is_recognized_tag_key("cycleway:right:oneway"));
+        assert!(is_recognized_tag_key("parking:lane:both"));
+        assert!(!is_recognized_tag_key("cycleway:right:oneway:bicycle"));
+        assert!(!is_recognized_tag_key("name:etymology:wikidata"));
+    }
+}
@@ -1,9 +1,18 @@
 use super::{Infer, Oneway};
 use crate::locale::Locale;
-use crate::tag::{Highway, TagKey, Tags};
+use crate::tag::{Highway, TagKey, TrackedTags};
 use crate::transform::tags_to_lanes::modes::BusLaneCount;
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
 
+/// Keys read by [`LanesDirectionScheme`] and [`CentreTurnLaneScheme`]
+pub(super) const KEYS: &[&str] = &[
+    "lanes",
+    "lanes:forward",
+    "lanes:backward",
+    "lanes:both_ways",
+    "centre_turn_lane",
+];
+
 /// The number of lanes for motor vehicle traffic
 #[derive(Debug)]
 pub enum Counts {
@@ -26,7 +35,7 @@ impl Counts {
         clippy::too_many_lines
     )]
     pub(super) fn new(
-        tags: &Tags,
+        tags: &TrackedTags,
         oneway: Oneway,
         highway: &Highway,
         centre_turn_lane: &CentreTurnLaneScheme, // TODO prefer TurnLanesScheme
@@ -212,7 +221,7 @@ pub(in crate::transform::tags_to_lanes) struct LanesDirectionScheme {
 }
 impl LanesDirectionScheme {
     pub fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         _oneway: Oneway,
         _locale: &Locale,
         warnings: &mut RoadWarnings,
@@ -246,7 +255,7 @@ impl CentreTurnLaneScheme {
     /// Parses and validates the `centre_turn_lane` tag and emits a deprecation warning.
     /// See <https://wiki.openstreetmap.org/wiki/Key:centre_turn_lane>.
     pub fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         _oneway: Oneway,
         _locale: &Locale,
         warnings: &mut RoadWarnings,
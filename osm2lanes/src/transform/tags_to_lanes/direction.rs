@@ -0,0 +1,192 @@
+//! Directions of the carriageway lanes from `direction:lanes=*`,
+//! a per lane scheme that is not yet established in OpenStreetMap.
+//! Each lane is `forward`, `backward` or `both`, left to right,
+//! e.g. for the current layout of a tidal road.
+
+use crate::locale::{DrivingSide, Locale};
+use crate::road::Direction;
+use crate::tag::{TagKey, TrackedTags, ONEWAY};
+use crate::transform::tags_to_lanes::{Infer, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+const DIRECTION_LANES: TagKey = TagKey::from("direction:lanes");
+const LANES: TagKey = TagKey::from("lanes");
+
+/// Keys read by [`lanes`]
+pub(super) const KEYS: &[&str] = &["direction:lanes"];
+
+/// Apply `direction:lanes=*` to the carriageway lanes, left to right.
+/// The value is ignored with a warning unless it has a valid direction for every lane,
+/// agrees with `oneway=*`, and goes from backward, through both ways, to forward,
+/// as checked by [`crate::road::Road::check_direction_order`].
+/// It takes precedence over `lanes:forward=*` and `lanes:backward=*`,
+/// with a warning when their counts disagree.
+pub(in crate::transform::tags_to_lanes) fn lanes(
+    tags: &TrackedTags,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let value = match tags.get(DIRECTION_LANES) {
+        Some(value) => value,
+        None => return,
+    };
+    let directions: Option<Vec<Direction>> = value
+        .split('|')
+        .map(|direction| match direction {
+            "forward" => Some(Direction::Forward),
+            "backward" => Some(Direction::Backward),
+            "both" => Some(Direction::Both),
+            _ => None,
+        })
+        .collect();
+    let directions = match directions {
+        Some(directions) => directions,
+        None => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(DIRECTION_LANES, value));
+            return;
+        },
+    };
+    if directions.len() != road.len() {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[DIRECTION_LANES, LANES]),
+        ));
+        return;
+    }
+    if bool::from(road.oneway) && directions.iter().any(|d| *d != Direction::Forward) {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[DIRECTION_LANES, ONEWAY]),
+        ));
+        return;
+    }
+    let rank = |direction: Direction| match (locale.driving_side, direction) {
+        (DrivingSide::Right, Direction::Backward) | (DrivingSide::Left, Direction::Forward) => 0_u8,
+        (_, Direction::Both) => 1,
+        (DrivingSide::Right, Direction::Forward) | (DrivingSide::Left, Direction::Backward) => 2,
+    };
+    if directions
+        .windows(2)
+        .any(|pair| matches!(pair, [left, right] if rank(*left) > rank(*right)))
+    {
+        warnings.push(TagsToLanesMsg::unsupported_tag(DIRECTION_LANES, value));
+        return;
+    }
+    let count = |direction: Direction| directions.iter().filter(|d| **d == direction).count();
+    let disagrees = |key: TagKey, direction: Direction| {
+        tags.get(key)
+            .and_then(|value| value.parse::<usize>().ok())
+            .map_or(false, |tagged| tagged != count(direction))
+    };
+    if disagrees(LANES + "forward", Direction::Forward)
+        || disagrees(LANES + "backward", Direction::Backward)
+    {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
+            DIRECTION_LANES,
+            LANES + "forward",
+            LANES + "backward",
+        ])));
+    }
+    for (lane, direction) in road.lanes_ltr_mut(locale).zip(directions) {
+        lane.direction = Infer::Direct(direction);
+    }
+    road.split_by_direction();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::road::{Direction, Lane};
+    use crate::tag::Tags;
+    use crate::transform::{
+        lanes_to_tags, tags_to_lanes, LanesToTagsConfig, RoadFromTags, TagsToLanesConfig,
+    };
+
+    fn road_from_tags(direction_lanes: &str) -> RoadFromTags {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "3"],
+            ["lanes:forward", "2"],
+            ["lanes:backward", "1"],
+            ["direction:lanes", direction_lanes],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap()
+    }
+
+    fn directions(road_from_tags: &RoadFromTags) -> Vec<Option<Direction>> {
+        road_from_tags
+            .road
+            .lanes
+            .iter()
+            .filter(|lane| lane.is_motor())
+            .map(|lane| match lane {
+                Lane::Travel { direction, .. } => *direction,
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tidal() {
+        // The counts of lanes:forward and lanes:backward are out of date
+        let tidal = road_from_tags("backward|backward|forward");
+        assert!(!tidal.warnings.is_empty());
+        assert_eq!(
+            directions(&tidal),
+            vec![
+                Some(Direction::Backward),
+                Some(Direction::Backward),
+                Some(Direction::Forward),
+            ]
+        );
+    }
+
+    #[test]
+    fn agrees_with_counts() {
+        let road_from_tags = road_from_tags("backward|forward|forward");
+        assert!(road_from_tags.warnings.is_empty());
+        assert_eq!(
+            directions(&road_from_tags),
+            vec![
+                Some(Direction::Backward),
+                Some(Direction::Forward),
+                Some(Direction::Forward),
+            ]
+        );
+    }
+
+    #[test]
+    fn roundtrip() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = road_from_tags("backward|backward|forward").road;
+        let tags = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(
+            tags.get("direction:lanes"),
+            Some("backward|backward|forward")
+        );
+        assert_eq!(tags.get("lanes:backward"), Some("2"));
+        let roundtrip =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        assert!(roundtrip.warnings.is_empty());
+        assert_eq!(roundtrip.road, road);
+    }
+
+    #[test]
+    fn invalid() {
+        let tagged = vec![
+            Some(Direction::Backward),
+            Some(Direction::Forward),
+            Some(Direction::Forward),
+        ];
+        for value in [
+            "backward|forward",
+            "backward|sideways|forward",
+            "forward|backward|forward",
+        ] {
+            let road_from_tags = road_from_tags(value);
+            assert!(!road_from_tags.warnings.is_empty(), "{}", value);
+            assert_eq!(directions(&road_from_tags), tagged, "{}", value);
+        }
+    }
+}
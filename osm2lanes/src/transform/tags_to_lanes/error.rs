@@ -2,6 +2,7 @@ use std::panic::Location;
 
 use serde::Serialize;
 
+use crate::road::ValidationWarning;
 use crate::tag::{DuplicateKeyError, TagKey, Tags};
 use crate::transform::tags_to_lanes::LaneBuilder;
 
@@ -51,6 +52,8 @@ pub enum TagsToLanesIssue {
         inside: LaneBuilder,
         outside: LaneBuilder,
     },
+    /// Implausible lanes built from the tags, see `Road::validate`
+    Implausible(ValidationWarning),
     /// Internal errors
     TagsDuplicateKey(DuplicateKeyError),
     Internal(&'static str),
@@ -219,6 +222,15 @@ impl TagsToLanesMsg {
         }
     }
 
+    #[must_use]
+    #[track_caller]
+    pub fn implausible(warning: ValidationWarning) -> Self {
+        TagsToLanesMsg {
+            location: Location::caller(),
+            issue: TagsToLanesIssue::Implausible(warning),
+        }
+    }
+
     #[must_use]
     #[track_caller]
     pub fn internal(e: &'static str) -> Self {
@@ -297,6 +309,9 @@ impl std::fmt::Display for TagsToLanesMsg {
                     inside, outside, self.location
                 )
             },
+            TagsToLanesIssue::Implausible(warning) => {
+                write!(f, "implausible: {} - {}", warning, self.location)
+            },
             TagsToLanesIssue::TagsDuplicateKey(e) => write!(f, "{} - {}", e, self.location),
             TagsToLanesIssue::Internal(e) => write!(f, "{} - {}", e, self.location),
         }
@@ -305,11 +320,102 @@ impl std::fmt::Display for TagsToLanesMsg {
 
 impl std::error::Error for TagsToLanesMsg {}
 
+impl TagsToLanesMsg {
+    /// Stable identifier of the kind of issue, see [`TagsToLanesIssue::code`]
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        self.issue.code()
+    }
+}
+
+impl TagsToLanesIssue {
+    /// Stable identifier of the kind of issue
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Deprecated { .. } => "deprecated",
+            Self::Unsupported { .. } => "unsupported",
+            Self::Unimplemented { .. } => "unimplemented",
+            Self::Ambiguous { .. } => "ambiguous",
+            Self::SeparatorLocaleUnused { .. } => "separator_locale_unused",
+            Self::SeparatorUnknown { .. } => "separator_unknown",
+            Self::Implausible(_) => "implausible",
+            Self::TagsDuplicateKey(_) => "tags_duplicate_key",
+            Self::Internal(_) => "internal",
+        }
+    }
+}
+
+/// Machine readable form of a `TagsToLanesMsg`
+#[derive(Serialize)]
+struct SerializedMsg<'msg> {
+    code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'msg str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<&'msg Tags>,
+    message: String,
+}
+
 impl Serialize for TagsToLanesMsg {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.collect_str(self)
+        let (description, tags) = match &self.issue {
+            TagsToLanesIssue::Deprecated {
+                deprecated_tags, ..
+            } => (None, Some(deprecated_tags)),
+            TagsToLanesIssue::Unsupported { description, tags }
+            | TagsToLanesIssue::Unimplemented { description, tags }
+            | TagsToLanesIssue::Ambiguous { description, tags } => {
+                (description.as_deref(), tags.as_ref())
+            },
+            TagsToLanesIssue::Internal(e) => (Some(*e), None),
+            TagsToLanesIssue::SeparatorLocaleUnused { .. }
+            | TagsToLanesIssue::SeparatorUnknown { .. }
+            | TagsToLanesIssue::Implausible(_)
+            | TagsToLanesIssue::TagsDuplicateKey(_) => (None, None),
+        };
+        SerializedMsg {
+            code: self.issue.code(),
+            description,
+            tags,
+            message: self.to_string(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TagsToLanesMsg;
+    use crate::road::ValidationWarning;
+    use crate::tag::Tags;
+
+    #[test]
+    fn serialize_warning() {
+        let msg =
+            TagsToLanesMsg::unsupported("foo without bar", Tags::from_str_pair(["foo", "yes"]));
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["code"], "unsupported");
+        assert_eq!(json["description"], "foo without bar");
+        assert_eq!(json["tags"], serde_json::json!({"foo": "yes"}));
+        assert!(json["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("unsupported: "));
+    }
+
+    #[test]
+    fn serialize_implausible() {
+        let msg =
+            TagsToLanesMsg::implausible(ValidationWarning::ParkingBetweenMotorLanes { index: 1 });
+        let json = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["code"], "implausible");
+        assert!(json["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("implausible: parking lane 1 is between motor lanes"));
     }
 }
@@ -0,0 +1,156 @@
+//! Indexed lane widths, `width:lane:<n>=*`,
+//! an alternative to the pipe separated `width:lanes=*` scheme.
+//! Lanes are numbered from 1, left to right, counting only the carriageway lanes.
+
+use crate::metric::Metre;
+use crate::road::Lane;
+use crate::tag::{TagKey, Tags, TrackedTags};
+use crate::transform::{RoadWarnings, TagsToLanesMsg};
+
+const WIDTH: TagKey = TagKey::from("width");
+
+/// Keys read by [`widths`]
+pub(super) const KEYS: &[&str] = &["width:lane:*"];
+
+/// Whether any `<key>:lane:<n>=*` tag is present
+fn has_indexed(tags: &TrackedTags, key: &TagKey) -> bool {
+    tags.tree_get(key.clone() + "lane").is_some()
+}
+
+/// Key and value of `<key>:lane:<n>=*` for the first `count` lanes
+fn indexed<'tags>(
+    tags: &TrackedTags<'tags>,
+    key: &TagKey,
+    count: usize,
+) -> Vec<Option<(TagKey, &'tags str)>> {
+    (1_usize..)
+        .take(count)
+        .map(|n| {
+            let key = key.clone() + "lane" + TagKey::from(n.to_string());
+            tags.get(&key).map(|value| (key, value))
+        })
+        .collect()
+}
+
+/// Warn about `<key>:lane:<n>=*` that does not index one of the first `count` lanes
+fn out_of_range(tags: &TrackedTags, key: &TagKey, count: usize, warnings: &mut RoadWarnings) {
+    let prefix = format!("{}:lane:", key.as_str());
+    for (k, v) in tags.prefixed(key.clone() + "lane") {
+        if let Some(n) = k.strip_prefix(&prefix) {
+            if !n
+                .parse::<usize>()
+                .map_or(false, |n| (1..=count).contains(&n))
+            {
+                warnings.push(TagsToLanesMsg::unsupported(
+                    "lane index out of range",
+                    Tags::from_str_pair([k, v]),
+                ));
+            }
+        }
+    }
+}
+
+/// Apply `width:lane:<n>=*` to the carriageway lanes,
+/// which are then no longer `defaulted_widths`
+pub(in crate::transform::tags_to_lanes) fn widths(
+    tags: &TrackedTags,
+    lanes: &mut [Lane],
+    defaulted_widths: &mut [bool],
+    warnings: &mut RoadWarnings,
+) {
+    if !has_indexed(tags, &WIDTH) {
+        return;
+    }
+    if tags.get(WIDTH + "lanes").is_some() {
+        warnings.push(TagsToLanesMsg::ambiguous_str(
+            "both width:lanes and width:lane:<n> present",
+        ));
+    }
+    let carriageway: Vec<(&mut Lane, &mut bool)> = lanes
+        .iter_mut()
+        .zip(defaulted_widths.iter_mut())
+        .filter(|(lane, _defaulted)| lane.is_motor() || lane.is_bus())
+        .collect();
+    out_of_range(tags, &WIDTH, carriageway.len(), warnings);
+    let values = indexed(tags, &WIDTH, carriageway.len());
+    for ((lane, defaulted), value) in carriageway.into_iter().zip(values) {
+        if let Some((key, value)) = value {
+            match (lane, value.parse::<f64>()) {
+                (Lane::Travel { width, .. }, Ok(value)) => {
+                    *width = Some(Metre::new(value));
+                    *defaulted = false;
+                },
+                _ => warnings.push(TagsToLanesMsg::unsupported_tag(key, value)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::Lane;
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    fn widths(tags: &Tags) -> (Vec<Option<Metre>>, bool) {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        let widths = road_from_tags
+            .road
+            .lanes
+            .iter()
+            .map(|lane| match lane {
+                Lane::Travel { width, .. } => *width,
+                _ => None,
+            })
+            .collect();
+        (widths, road_from_tags.warnings.is_empty())
+    }
+
+    #[test]
+    fn width_lane_1() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["width:lane:1", "3.0"],
+        ])
+        .unwrap();
+        let (widths, no_warnings) = widths(&tags);
+        assert_eq!(widths.first(), Some(&Some(Metre::new(3.0))));
+        assert_ne!(widths.get(1), Some(&Some(Metre::new(3.0))));
+        assert!(no_warnings);
+    }
+
+    #[test]
+    fn width_lane_and_lanes() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["width:lane:1", "3.0"],
+            ["width:lanes", "3.0|3.5"],
+        ])
+        .unwrap();
+        let (_widths, no_warnings) = widths(&tags);
+        assert!(!no_warnings);
+    }
+
+    #[test]
+    fn width_lane_out_of_range() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["width:lane:2", "3.0"],
+            ["width:lane:5", "3.0"],
+        ])
+        .unwrap();
+        let (widths, no_warnings) = widths(&tags);
+        assert_eq!(widths.get(1), Some(&Some(Metre::new(3.0))));
+        assert!(!no_warnings);
+    }
+}
@@ -1,8 +1,8 @@
 #![allow(clippy::module_name_repetitions)] // TODO: fix upstream
 
-use crate::locale::Locale;
-use crate::road::Road;
-use crate::tag::Tags;
+use crate::locale::{DrivingSide, Locale};
+use crate::road::{Lane, Road};
+use crate::tag::{Highway, TagKey, Tags, TrackedTags, HIGHWAY, ONEWAY};
 use crate::transform::error::{RoadError, RoadWarnings};
 use crate::transform::RoadFromTags;
 
@@ -13,6 +13,8 @@ mod access_by_lane;
 
 mod counts;
 
+mod lane_index;
+
 mod modes;
 
 mod separator;
@@ -23,10 +25,30 @@ use road::{LaneBuilder, LaneBuilderError, LaneType, RoadBuilder};
 mod unsupported;
 use unsupported::unsupported;
 
+mod width;
+
+mod direction;
+
+const TRACKTYPE: TagKey = TagKey::from("tracktype");
+
 #[non_exhaustive]
 pub struct Config {
     pub error_on_warnings: bool,
     pub include_separators: bool,
+    /// Error when the way has no `highway=*`, or an unknown one,
+    /// otherwise warn and treat it as `highway=road`
+    pub require_highway: bool,
+    /// Driving side to use for this way instead of the one from the `Locale`
+    pub driving_side_override: Option<DrivingSide>,
+    /// Give separators their marking widths, or zero width so that lanes pack edge-to-edge
+    pub separator_widths: bool,
+    /// Treat a cycle lane with an unknown `cycleway:<side>:lane=*` as advisory,
+    /// otherwise as exclusive
+    pub unknown_cycle_lane_advisory: bool,
+    /// Warn when `width=*` is too narrow for the number of carriageway lanes
+    pub check_lane_width: bool,
+    /// Error on the first deprecated tag, e.g. `cycleway=opposite_lane`, instead of warning
+    pub reject_deprecated: bool,
 }
 
 impl Config {
@@ -35,6 +57,7 @@ impl Config {
         Self {
             error_on_warnings,
             include_separators,
+            ..Self::default()
         }
     }
 }
@@ -44,6 +67,12 @@ impl Default for Config {
         Self {
             error_on_warnings: false,
             include_separators: true,
+            require_highway: true,
+            driving_side_override: None,
+            separator_widths: true,
+            unknown_cycle_lane_advisory: false,
+            check_lane_width: false,
+            reject_deprecated: false,
         }
     }
 }
@@ -51,9 +80,12 @@ impl Default for Config {
 mod oneway {
     use super::TagsToLanesMsg;
     use crate::locale::Locale;
-    use crate::tag::{Tags, ONEWAY};
+    use crate::tag::{Highway, HighwayType, TrackedTags, ONEWAY};
     use crate::transform::RoadWarnings;
 
+    /// Keys read by [`Oneway::from_tags`]
+    pub const KEYS: &[&str] = &["oneway", "junction"];
+
     #[derive(Clone, Copy, PartialEq)]
     pub enum Oneway {
         Yes,
@@ -81,25 +113,31 @@ mod oneway {
 
     impl Oneway {
         pub fn from_tags(
-            tags: &Tags,
+            tags: &TrackedTags,
+            highway: &Highway,
             _locale: &Locale,
             _warnings: &mut RoadWarnings,
         ) -> Result<Self, TagsToLanesMsg> {
-            Ok(
-                match (tags.get(ONEWAY), tags.is("junction", "roundabout")) {
-                    (Some("yes"), _) => Self::Yes,
-                    (Some("no"), false) => Self::No,
-                    (Some("no"), true) => {
-                        return Err(TagsToLanesMsg::ambiguous_tags(
-                            tags.subset(&["oneway", "junction"]),
-                        ));
-                    },
-                    (Some(value), _) => {
-                        return Err(TagsToLanesMsg::unimplemented_tag(ONEWAY, value));
-                    },
-                    (None, roundabout) => Self::from(roundabout),
+            // Roundabouts and other circular junctions are implicitly oneway,
+            // <https://wiki.openstreetmap.org/wiki/Tag:junction%3Droundabout>
+            // <https://wiki.openstreetmap.org/wiki/Tag:junction%3Dcircular>
+            let roundabout = tags.is_any("junction", &["roundabout", "circular"]);
+            // Link roads, such as slip roads, are usually oneway unless tagged `oneway=no`,
+            // <https://wiki.openstreetmap.org/wiki/Highway_link>
+            let link = matches!(highway.r#type(), HighwayType::Link(_));
+            Ok(match (tags.get(ONEWAY), roundabout) {
+                (Some("yes"), _) => Self::Yes,
+                (Some("no"), false) => Self::No,
+                (Some("no"), true) => {
+                    return Err(TagsToLanesMsg::ambiguous_tags(
+                        tags.subset(&["oneway", "junction"]),
+                    ));
+                },
+                (Some(value), _) => {
+                    return Err(TagsToLanesMsg::unimplemented_tag(ONEWAY, value));
                 },
-            )
+                (None, roundabout) => Self::from(roundabout || link),
+            })
         }
     }
 }
@@ -108,6 +146,51 @@ use oneway::Oneway;
 mod infer;
 pub use infer::Infer;
 
+/// Keys read by the steps of the transform, see [`recognized_tag_keys`]
+const KEYS: &[&[&str]] = &[
+    road::KEYS,
+    oneway::KEYS,
+    counts::KEYS,
+    lane_index::KEYS,
+    width::KEYS,
+    direction::KEYS,
+    separator::KEYS,
+    unsupported::KEYS,
+    // Kept on the road as is
+    &["surface", "oneway:conditional", "layer"],
+    // Read by `track_grade`
+    &["tracktype"],
+];
+
+/// Keys of the OSM tags that are understood when transforming tags to lanes,
+/// where a `*` part matches any single part of a key, e.g. `cycleway:*:oneway`.
+/// Each step of the transform declares the keys that it reads.
+pub fn recognized_tag_keys() -> impl Iterator<Item = &'static str> {
+    KEYS.iter()
+        .chain(modes::KEYS)
+        .flat_map(|keys| keys.iter().copied())
+}
+
+/// Whether the key of an OSM tag is understood when transforming tags to lanes,
+/// see [`recognized_tag_keys`]
+///
+/// ```
+/// use osm2lanes::transform::is_recognized_tag_key;
+/// assert!(is_recognized_tag_key("lanes"));
+/// assert!(is_recognized_tag_key("cycleway:left:oneway"));
+/// assert!(!is_recognized_tag_key("name"));
+/// ```
+#[must_use]
+pub fn is_recognized_tag_key(key: &str) -> bool {
+    recognized_tag_keys().any(|pattern| {
+        pattern.split(':').count() == key.split(':').count()
+            && pattern
+                .split(':')
+                .zip(key.split(':'))
+                .all(|(pattern, part)| pattern == "*" || pattern == part)
+    })
+}
+
 /// From an OpenStreetMap way's tags,
 /// determine the lanes along the road from left to right.
 ///
@@ -128,6 +211,28 @@ pub fn tags_to_lanes(
     config: &Config,
 ) -> Result<RoadFromTags, RoadError> {
     let mut warnings = RoadWarnings::default();
+    // Records the tags that are read, see `RoadFromTags::consumed_tags`
+    let tracked = TrackedTags::new(tags);
+    let tags = &tracked;
+
+    if config.require_highway {
+        match Highway::from_tags(tags) {
+            Err(None) => return Err(TagsToLanesMsg::unsupported_str("way is not highway").into()),
+            Err(Some(s)) => return Err(TagsToLanesMsg::unsupported_tag(HIGHWAY, &s).into()),
+            Ok(_) => {},
+        }
+    }
+
+    let overridden_locale;
+    let locale = if let Some(driving_side) = config.driving_side_override {
+        overridden_locale = Locale {
+            driving_side,
+            ..locale.clone()
+        };
+        &overridden_locale
+    } else {
+        locale
+    };
 
     // Early return if we find unimplemented or unsupported tags.
     unsupported(tags, locale, &mut warnings)?;
@@ -137,25 +242,628 @@ pub fn tags_to_lanes(
 
     modes::non_motorized(tags, locale, &mut road, &mut warnings)?;
 
+    // Bus lanes are made from the outside motor lanes,
+    // so this comes before any other lanes are pushed outside of them.
     modes::bus(tags, locale, &mut road, &mut warnings)?;
 
-    modes::bicycle(tags, locale, &mut road, &mut warnings)?;
+    // Also matched to the motor and bus lanes alone.
+    modes::hgv(tags, locale, &mut road, &mut warnings)?;
+
+    // Also matched to the motor and bus lanes alone, before the separators are chosen.
+    direction::lanes(tags, locale, &mut road, &mut warnings);
+
+    // Also matched to the motor and bus lanes alone, once their directions are known.
+    modes::max_speed(tags, locale, &mut road, &mut warnings);
+
+    modes::bicycle(tags, locale, config, &mut road, &mut warnings)?;
+
+    // Matched to the motor and bus lanes alone, once the cycle lanes are known.
+    modes::turns(tags, locale, &mut road, &mut warnings)?;
 
-    modes::parking(tags, locale, &mut road)?;
+    // Pushed inside of the motor lanes, so after everything matched to them alone.
+    modes::rail(tags, locale, &mut road, &mut warnings);
 
+    modes::parking(tags, locale, &mut road, &mut warnings)?;
+
+    // Last, so that sidewalks and shoulders are the outermost,
+    // outside of any cycle tracks and parking.
     modes::foot_and_shoulder(tags, locale, &mut road, &mut warnings)?;
 
-    let (lanes, highway, _oneway) =
+    // Whether the width of each lane was defaulted, left to right, see `Road::recompute_defaults`
+    let defaulted: Vec<bool> = road
+        .lanes_ltr(locale)
+        .map(|lane| matches!(lane.width.target, Infer::Default(_)))
+        .collect();
+
+    let (mut lanes, highway, _oneway) =
         road.into_ltr(tags, locale, config.include_separators, &mut warnings)?;
+    let mut defaulted_widths = with_separators(&lanes, defaulted);
+
+    if !config.separator_widths {
+        zero_separator_widths(&mut lanes);
+    }
+
+    width::lanes(tags, &mut lanes, &mut defaulted_widths, &mut warnings);
+    lane_index::widths(tags, &mut lanes, &mut defaulted_widths, &mut warnings);
+    width::check_total(tags, &lanes, &mut warnings);
+    if config.check_lane_width {
+        width::check_lane_count(tags, &lanes, &mut warnings);
+    }
+
+    let bus_bay = modes::bus_bay(tags, locale, &mut warnings);
+    let max_weight = modes::max_weight(tags, &mut warnings);
+    let track_grade = track_grade(tags, &mut warnings);
+
+    let mut road = Road::from_lanes(lanes, highway);
+    road.bus_bay = bus_bay;
+    road.surface = tags.get("surface").map(str::to_owned);
+    road.max_weight = max_weight;
+    road.max_speed_hgv = modes::vehicle_max_speed(tags, "hgv", locale, &mut warnings);
+    road.max_speed_bus = modes::vehicle_max_speed(tags, "bus", locale, &mut warnings);
+    road.track_grade = track_grade;
+    road.oneway_conditional = tags.get(ONEWAY + "conditional").map(str::to_owned);
+    road.layer = tags.get_parsed("layer", &mut warnings).unwrap_or(0);
+    road.is_area = modes::is_area(tags);
+    road.set_defaults(locale, defaulted_widths);
+
+    for warning in road.validate() {
+        warnings.push(TagsToLanesMsg::implausible(warning));
+    }
 
     let road_from_tags = RoadFromTags {
-        road: Road { lanes, highway },
+        road,
         warnings,
+        consumed: tracked.into_read(),
     };
 
+    if config.reject_deprecated {
+        if let Some(deprecated) = road_from_tags
+            .warnings
+            .iter()
+            .find(|warning| warning.code() == "deprecated")
+        {
+            return Err(deprecated.clone().into());
+        }
+    }
+
     if config.error_on_warnings && !road_from_tags.warnings.is_empty() {
         return Err(road_from_tags.warnings.into());
     }
 
     Ok(road_from_tags)
 }
+
+/// [`tags_to_lanes`] from and to JSON, for the web worker.
+/// The tags are a JSON object of strings.
+/// Returns a serialized [`TransformResult`](crate::transform::TransformResult),
+/// or a serialized [`RoadError`], also when the result cannot be serialized.
+///
+/// # Panics
+///
+/// The [`RoadError`] cannot be serialized.
+#[cfg(feature = "json")]
+#[must_use]
+pub fn tags_to_lanes_json(tags_json: &str, locale: &Locale, config: &Config) -> String {
+    use crate::transform::TransformResult;
+
+    serde_json::from_str::<Tags>(tags_json)
+        .map_err(|_e| RoadError::from(TagsToLanesMsg::unsupported_str("tags are not JSON")))
+        .and_then(|tags| tags_to_lanes(&tags, locale, config))
+        .and_then(|road_from_tags| {
+            serde_json::to_string(&TransformResult::from(road_from_tags))
+                .map_err(|_e| RoadError::from(TagsToLanesMsg::internal("result is not JSON")))
+        })
+        .unwrap_or_else(|e| serde_json::to_string(&e).expect("RoadError is not JSON"))
+}
+
+impl Road {
+    /// Replace the separators of the road with those [`tags_to_lanes`] infers
+    /// from the composition and directions of its lanes, without any tags,
+    /// e.g. for a road built without separators.
+    ///
+    /// # Errors
+    ///
+    /// - The road has no lanes
+    /// - Any warnings, if the config treats them as errors
+    pub fn add_default_separators(
+        &mut self,
+        locale: &Locale,
+        config: &Config,
+    ) -> Result<RoadWarnings, RoadError> {
+        let mut warnings = RoadWarnings::default();
+        let (lanes, _highway, _oneway) = RoadBuilder::from_road(self, locale).into_ltr(
+            &TrackedTags::new(&Tags::default()),
+            locale,
+            true,
+            &mut warnings,
+        )?;
+        if config.error_on_warnings && !warnings.is_empty() {
+            return Err(warnings.into());
+        }
+        self.replace_separators(lanes);
+        if !config.separator_widths {
+            zero_separator_widths(&mut self.lanes);
+        }
+        Ok(warnings)
+    }
+
+    /// Apply the separator options of the config to an already built road, without the tags:
+    /// separators are removed without [`Config::include_separators`],
+    /// or else added with [`Road::add_default_separators`] if the road has none.
+    /// Existing separators are kept, as they may come from tags.
+    ///
+    /// # Errors
+    ///
+    /// See [`Road::add_default_separators`]
+    pub fn apply_config(
+        &mut self,
+        config: &Config,
+        locale: &Locale,
+    ) -> Result<RoadWarnings, RoadError> {
+        if !config.include_separators {
+            self.remove_separators();
+            return Ok(RoadWarnings::default());
+        }
+        if !self.has_separators() {
+            return self.add_default_separators(locale, config);
+        }
+        if !config.separator_widths {
+            zero_separator_widths(&mut self.lanes);
+        }
+        Ok(RoadWarnings::default())
+    }
+}
+
+/// Spread whether the width of each lane other than the separators was defaulted
+/// over all of the `lanes`, see [`Road::defaulted_widths`]
+fn with_separators(lanes: &[Lane], defaulted: Vec<bool>) -> Vec<bool> {
+    let mut defaulted = defaulted.into_iter();
+    lanes
+        .iter()
+        .map(|lane| !lane.is_separator() && defaulted.next().unwrap_or(false))
+        .collect()
+}
+
+/// Zero width separators, see [`Config::separator_widths`]
+fn zero_separator_widths(lanes: &mut [Lane]) {
+    for lane in lanes {
+        if let Lane::Separator { markings } = lane {
+            markings.zero_widths();
+        }
+    }
+}
+
+/// Grade of a track from `tracktype=grade<N>`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:tracktype>
+fn track_grade(tags: &TrackedTags, warnings: &mut RoadWarnings) -> Option<u8> {
+    let value = tags.get(TRACKTYPE)?;
+    match value {
+        "grade1" => Some(1),
+        "grade2" => Some(2),
+        "grade3" => Some(3),
+        "grade4" => Some(4),
+        "grade5" => Some(5),
+        _ => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(TRACKTYPE, value));
+            None
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tags_to_lanes, Config};
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Speed;
+    use crate::road::{Designated, Direction, Lane, Style};
+    use crate::tag::Tags;
+    use crate::transform::{lanes_to_tags, LanesToTagsConfig, RoadError};
+
+    #[test]
+    fn oneway_conditional() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "primary"],
+            ["lanes", "2"],
+            ["oneway", "yes"],
+            ["oneway:conditional", "-1 @ (Mo-Fr 16:00-19:00)"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &Config::new(false, false))
+            .unwrap()
+            .road;
+        assert_eq!(
+            road.oneway_conditional.as_deref(),
+            Some("-1 @ (Mo-Fr 16:00-19:00)")
+        );
+        assert!(road
+            .lanes
+            .iter()
+            .filter(|lane| lane.is_motor())
+            .all(|lane| matches!(
+                lane,
+                Lane::Travel {
+                    direction: Some(Direction::Forward),
+                    ..
+                }
+            )));
+
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(
+            roundtrip.get("oneway:conditional"),
+            Some("-1 @ (Mo-Fr 16:00-19:00)")
+        );
+    }
+
+    #[test]
+    fn layer() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["tunnel", "yes"],
+            ["layer", "-1"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &Config::new(false, false))
+            .unwrap()
+            .road;
+        assert_eq!(road.layer, -1);
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("layer"), Some("-1"));
+
+        let tags = Tags::from_str_pairs(&[["highway", "residential"]]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &Config::default())
+            .unwrap()
+            .road;
+        assert_eq!(road.layer, 0);
+    }
+
+    #[test]
+    fn reject_deprecated() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["cycleway", "opposite_lane"],
+        ])
+        .unwrap();
+        let road_from_tags = tags_to_lanes(&tags, &locale, &Config::default()).unwrap();
+        assert!(road_from_tags
+            .warnings
+            .iter()
+            .any(|warning| warning.code() == "deprecated"));
+        let config = Config {
+            reject_deprecated: true,
+            ..Config::default()
+        };
+        match tags_to_lanes(&tags, &locale, &config) {
+            Err(RoadError::Msg(msg)) => assert_eq!(msg.code(), "deprecated"),
+            result => panic!("expected a deprecated error, got {:?}", result),
+        }
+        // Other warnings are still only warnings
+        let tags = Tags::from_str_pairs(&[["highway", "residential"], ["layer", "x"]]).unwrap();
+        let road_from_tags = tags_to_lanes(&tags, &locale, &config).unwrap();
+        assert!(!road_from_tags.warnings.is_empty());
+    }
+
+    #[test]
+    fn consumed_tags() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["maxspeed", "30"],
+            ["name", "Main Street"],
+        ])
+        .unwrap();
+        let road_from_tags = tags_to_lanes(&tags, &locale, &Config::default()).unwrap();
+        let consumed = road_from_tags.consumed_tags();
+        assert_eq!(
+            consumed.to_vec(),
+            vec!["highway=residential", "lanes=2", "maxspeed=30"]
+        );
+        // The tags are left untouched, so a second transform consumes the same
+        let again = tags_to_lanes(&tags, &locale, &Config::default()).unwrap();
+        assert_eq!(again.consumed_tags().to_vec(), consumed.to_vec());
+    }
+
+    #[test]
+    fn add_default_separators() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        for tags in [
+            vec![
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["sidewalk", "both"],
+            ],
+            vec![["highway", "primary"], ["lanes", "4"], ["shoulder", "yes"]],
+            vec![["highway", "secondary"], ["oneway", "yes"], ["lanes", "2"]],
+        ] {
+            let tags = Tags::from_str_pairs(&tags).unwrap();
+            let road = tags_to_lanes(&tags, &locale, &Config::new(false, true))
+                .unwrap()
+                .road;
+            let mut stripped = road.clone().without_separators();
+            stripped
+                .add_default_separators(&locale, &Config::new(false, true))
+                .unwrap();
+            assert_eq!(stripped, road, "{:?}", tags);
+        }
+    }
+
+    #[test]
+    fn apply_config() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let with = tags_to_lanes(&tags, &locale, &Config::new(false, true))
+            .unwrap()
+            .road;
+        let without = tags_to_lanes(&tags, &locale, &Config::new(false, false))
+            .unwrap()
+            .road;
+
+        let mut road = with.clone();
+        road.apply_config(&Config::new(false, false), &locale)
+            .unwrap();
+        assert_eq!(road, without);
+        road.apply_config(&Config::new(false, true), &locale)
+            .unwrap();
+        assert_eq!(road, with);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn tags_to_lanes_json() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let json = super::tags_to_lanes_json(
+            r#"{"highway": "residential", "lanes": "2", "sidewalk": "yes"}"#,
+            &locale,
+            &Config::new(false, false),
+        );
+        let result: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(result["road"]["highway"], "residential");
+        assert_eq!(result["road"]["lanes"].as_array().unwrap().len(), 4);
+        assert_eq!(result["codes"], serde_json::json!(["ambiguous"]));
+        assert_eq!(
+            result["warnings"].as_array().unwrap().len(),
+            result["codes"].as_array().unwrap().len()
+        );
+
+        let json = super::tags_to_lanes_json("[]", &locale, &Config::default());
+        let error: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(error["error"]["code"], "unsupported");
+    }
+
+    #[test]
+    fn separator_widths() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["sidewalk", "both"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = |separator_widths: bool| {
+            let config = Config {
+                separator_widths,
+                ..Config::new(false, true)
+            };
+            tags_to_lanes(&tags, &locale, &config).unwrap().road
+        };
+        let with = road(true);
+        let without = road(false);
+        assert!(without.has_separators());
+        assert!(without.width(&locale) < with.width(&locale));
+        assert_eq!(
+            without.width(&locale),
+            with.without_separators().width(&locale)
+        );
+    }
+
+    #[test]
+    fn bus_lane_beside_bicycle_lane() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "primary"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["busway", "lane"],
+            ["cycleway:right", "lane"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let lanes = tags_to_lanes(&tags, &locale, &Config::new(false, true))
+            .unwrap()
+            .road
+            .lanes;
+        let designated: Vec<Designated> = lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel { designated, .. } => Some(*designated),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            designated,
+            vec![Designated::Motor, Designated::Bus, Designated::Bicycle]
+        );
+        let markings = lanes
+            .windows(2)
+            .find_map(|pair| match pair {
+                [Lane::Travel {
+                    designated: Designated::Bus,
+                    ..
+                }, Lane::Separator { markings }] => Some(markings),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            markings
+                .iter()
+                .map(|marking| marking.style)
+                .collect::<Vec<_>>(),
+            vec![Style::SolidLine]
+        );
+    }
+
+    #[test]
+    fn require_highway() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let lenient = Config {
+            require_highway: false,
+            ..Config::default()
+        };
+        for tags in [
+            Tags::from_str_pairs(&[["lanes", "2"]]).unwrap(),
+            Tags::from_str_pairs(&[["highway", "motorroad"], ["lanes", "2"]]).unwrap(),
+        ] {
+            assert!(tags_to_lanes(&tags, &locale, &Config::default()).is_err());
+            let road_from_tags = tags_to_lanes(&tags, &locale, &lenient).unwrap();
+            assert!(!road_from_tags.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn driving_side_override() {
+        let tags = Tags::from_str_pairs(&[["highway", "residential"], ["lanes", "2"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let first_direction = |config: &Config| {
+            let road = tags_to_lanes(&tags, &locale, config).unwrap().road;
+            match road.lanes.first() {
+                Some(Lane::Travel { direction, .. }) => *direction,
+                _ => None,
+            }
+        };
+        assert_eq!(
+            first_direction(&Config::new(false, false)),
+            Some(Direction::Backward)
+        );
+        let config = Config {
+            driving_side_override: Some(DrivingSide::Left),
+            ..Config::new(false, false)
+        };
+        assert_eq!(first_direction(&config), Some(Direction::Forward));
+    }
+
+    #[test]
+    fn raceway() {
+        let tags = Tags::from_str_pairs(&[["highway", "raceway"], ["lanes", "2"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &Config::new(false, false))
+            .unwrap()
+            .road;
+        assert_eq!(road.lanes.len(), 2);
+        for lane in &road.lanes {
+            match lane {
+                Lane::Travel {
+                    designated: Designated::Motor,
+                    width: Some(width),
+                    ..
+                } => assert!(width.val() > 3.5_f64),
+                lane => panic!("expected wide motor lane, got {:?}", lane),
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_highway_type() {
+        let tags = Tags::from_str_pairs(&[["highway", "bus_guideway"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        assert!(tags_to_lanes(&tags, &locale, &Config::default()).is_err());
+    }
+
+    #[test]
+    fn access_no_bicycle_designated() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "service"],
+            ["access", "no"],
+            ["bicycle", "designated"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &Config::new(false, false))
+            .unwrap()
+            .road;
+        assert!(!road.lanes.is_empty());
+        assert!(road.lanes.iter().all(Lane::is_bicycle));
+    }
+
+    #[test]
+    fn access_no_bus_and_psv() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        for mode in ["bus", "psv"] {
+            let tags =
+                Tags::from_str_pairs(&[["highway", "service"], ["access", "no"], [mode, "yes"]])
+                    .unwrap();
+            let road = tags_to_lanes(&tags, &locale, &Config::new(false, false))
+                .unwrap()
+                .road;
+            assert!(!road.lanes.is_empty());
+            assert!(road.lanes.iter().all(Lane::is_bus));
+        }
+    }
+
+    #[test]
+    fn maxspeed_pipe() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["maxspeed", "50|30"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = tags_to_lanes(&tags, &locale, &Config::new(false, false)).unwrap();
+        let max_speeds: Vec<Option<Speed>> = road_from_tags
+            .road
+            .lanes
+            .iter()
+            .map(|lane| match lane {
+                Lane::Travel { max_speed, .. } => *max_speed,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            max_speeds,
+            vec![Some(Speed::Kph(50.0)), Some(Speed::Kph(30.0))]
+        );
+        assert!(!road_from_tags.warnings.is_empty());
+    }
+
+    #[test]
+    fn maxspeed_lanes_faster_than_road() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = |max_speed_lanes: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "primary"],
+                ["oneway", "yes"],
+                ["lanes", "2"],
+                ["maxspeed", "50"],
+                ["maxspeed:lanes", max_speed_lanes],
+            ])
+            .unwrap();
+            tags_to_lanes(&tags, &locale, &Config::new(false, false)).unwrap()
+        };
+        let slower = road_from_tags("50|30");
+        assert!(slower.warnings.is_empty());
+        let faster = road_from_tags("50|70");
+        assert!(!faster.warnings.is_empty());
+        let max_speeds: Vec<Option<Speed>> = faster
+            .road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel { max_speed, .. } => Some(*max_speed),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            max_speeds,
+            vec![Some(Speed::Kph(50.0)), Some(Speed::Kph(70.0))]
+        );
+    }
+}
@@ -1,15 +1,32 @@
 use crate::locale::Locale;
-use crate::road::{Designated, Direction};
-use crate::tag::Tags;
+use crate::metric::Metre;
+use crate::road::{Color, Cycleway, Designated, Direction, Style};
+use crate::tag::{TagKey, TrackedTags};
 use crate::transform::tags::CYCLEWAY;
 use crate::transform::tags_to_lanes::oneway::Oneway;
 use crate::transform::tags_to_lanes::road::LaneType;
-use crate::transform::tags_to_lanes::{Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
+use crate::transform::tags_to_lanes::separator::LaneChange;
+use crate::transform::tags_to_lanes::{Config, Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
 use crate::transform::{RoadWarnings, WaySide};
 
+const CONTRAFLOW_TRACK_BUFFER: Metre = Metre::new(0.5);
+/// Painted buffer of `cycleway:<side>:separation=hatched_area`, guessed
+const HATCHED_AREA_WIDTH: Metre = Metre::new(0.5);
+
+/// Keys read by [`bicycle`]
+pub(super) const KEYS: &[&str] = &[
+    "cycleway",
+    "cycleway:*",
+    "cycleway:*:oneway",
+    "cycleway:*:lane",
+    "cycleway:*:separation",
+    "cycleway:*:separation:colour",
+    "oneway:bicycle",
+];
+
 struct UnknownVariant;
 
-impl Tags {
+impl TrackedTags<'_> {
     fn get_variant<T: AsRef<str>>(&self, k: T) -> Result<Option<Variant>, UnknownVariant> {
         match self.get(k) {
             Some("lane") => Ok(Some(Variant::Lane)),
@@ -33,6 +50,15 @@ pub(in crate::transform::tags_to_lanes) enum Variant {
     Track,
 }
 
+impl From<Variant> for Cycleway {
+    fn from(variant: Variant) -> Self {
+        match variant {
+            Variant::Lane => Self::Lane,
+            Variant::Track => Self::Track,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub(in crate::transform::tags_to_lanes) struct Way {
     variant: Variant,
@@ -55,7 +81,7 @@ pub(in crate::transform::tags_to_lanes) struct Scheme(Location);
 impl Scheme {
     #[allow(clippy::unnecessary_wraps, clippy::too_many_lines)]
     pub(in crate::transform::tags_to_lanes) fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         road_oneway: Oneway,
         warnings: &mut RoadWarnings,
@@ -110,115 +136,222 @@ impl Scheme {
                 },
             }))
         } else {
-            // cycleway=opposite_lane
-            if tags.is(CYCLEWAY, "opposite_lane") {
-                warnings.push(TagsToLanesMsg::deprecated_tags(
-                    tags.subset(&["cycleway", "oneway"]),
-                ));
-                return Ok(Self(Location::Backward(Way {
-                    variant: Variant::Lane,
-                    direction: Direction::Backward,
-                })));
-            }
-            // cycleway=opposite oneway=yes oneway:bicycle=no
-            if tags.is(CYCLEWAY, "opposite") {
-                if !(road_oneway.into() && tags.is("oneway:bicycle", "no")) {
-                    return Err(TagsToLanesMsg::unsupported_str(
-                        "cycleway=opposite without oneway=yes oneway:bicycle=no",
-                    ));
-                }
-                return Ok(Self(Location::Backward(Way {
-                    variant: Variant::Lane,
-                    direction: Direction::Backward,
-                })));
+            if let Some(location) = opposite(tags, locale, road_oneway, warnings) {
+                return Ok(Self(location));
             }
+            let forward_key = CYCLEWAY + locale.driving_side.tag();
+            let backward_key = CYCLEWAY + locale.driving_side.opposite().tag();
+            let forward_variant = tags
+                .cycleway_variant(Some(locale.driving_side.into()))
+                .ok()
+                .flatten();
+            let backward_variant = tags
+                .cycleway_variant(Some(locale.driving_side.opposite().into()))
+                .ok()
+                .flatten();
+            // With a cycleway on only one side, it is the one to cycle both ways on
+            let single = forward_variant.is_none() || backward_variant.is_none();
+            let bicycle_both_ways = tags.is("oneway:bicycle", "no");
             // cycleway:FORWARD=*
-            if let Ok(Some(variant)) = tags.cycleway_variant(Some(locale.driving_side.into())) {
-                if tags.is(CYCLEWAY + locale.driving_side.tag() + "oneway", "no")
-                    || tags.is("oneway:bicycle", "no")
+            let forward = forward_variant.map(|variant| Way {
+                variant,
+                direction: if tags.is(forward_key + "oneway", "no") || (single && bicycle_both_ways)
                 {
-                    return Ok(Self(Location::Forward(Way {
-                        variant,
-                        direction: Direction::Both,
-                    })));
-                }
-                return Ok(Self(Location::Forward(Way {
-                    variant,
-                    direction: Direction::Forward,
-                })));
-            }
-            // cycleway:FORWARD=opposite_lane
-            if tags.is_any(
-                CYCLEWAY + locale.driving_side.tag(),
-                &["opposite_lane", "opposite_track"],
-            ) {
-                warnings.push(TagsToLanesMsg::deprecated_tags(
-                    tags.subset(&[CYCLEWAY + locale.driving_side.tag()]),
-                ));
-                return Ok(Self(Location::Forward(Way {
-                    variant: Variant::Lane, // TODO distinguish oposite_ values
-                    direction: Direction::Backward,
-                })));
-            }
+                    Direction::Both
+                } else {
+                    Direction::Forward
+                },
+            });
             // cycleway:BACKWARD=*
-            if let Ok(Some(variant)) =
-                tags.cycleway_variant(Some(locale.driving_side.opposite().into()))
-            {
-                return Ok(Self(
-                    if tags.is(
-                        CYCLEWAY + locale.driving_side.opposite().tag() + "oneway",
-                        "yes",
-                    ) {
-                        Location::Backward(Way {
-                            variant,
-                            direction: Direction::Forward,
-                        })
-                    } else if tags.is(
-                        CYCLEWAY + locale.driving_side.opposite().tag() + "oneway",
-                        "-1",
-                    ) {
-                        Location::Backward(Way {
-                            variant,
-                            direction: Direction::Backward,
-                        })
-                    } else if tags.is(
-                        CYCLEWAY + locale.driving_side.opposite().tag() + "oneway",
-                        "no",
-                    ) || tags.is("oneway:bicycle", "no")
-                    {
-                        Location::Backward(Way {
-                            variant,
-                            direction: Direction::Both,
-                        })
-                    } else if road_oneway.into() {
-                        // A oneway road with a cycleway on the wrong side
-                        Location::Backward(Way {
-                            variant,
-                            direction: Direction::Forward,
-                        })
-                    } else {
-                        // A contraflow bicycle lane
-                        Location::Backward(Way {
-                            variant,
-                            direction: Direction::Backward,
-                        })
-                    },
-                ));
-            }
-            // cycleway:BACKWARD=opposite_lane
-            if tags.is_any(
-                CYCLEWAY + locale.driving_side.opposite().tag(),
-                &["opposite_lane", "opposite_track"],
-            ) {
-                return Err(TagsToLanesMsg::unsupported_tags(
-                    tags.subset(&[CYCLEWAY + locale.driving_side.opposite().tag()]),
-                ));
-            }
-            Ok(Self(Location::None))
+            let backward = backward_variant.map(|variant| Way {
+                variant,
+                direction: match tags.get(backward_key + "oneway") {
+                    Some("yes") => Direction::Forward,
+                    Some("-1") => Direction::Backward,
+                    Some("no") => Direction::Both,
+                    _ if bicycle_both_ways && single => Direction::Both,
+                    // A contraflow bicycle lane, with the forward cycleway on the other side
+                    _ if bicycle_both_ways => Direction::Backward,
+                    // A oneway road with a cycleway on the wrong side
+                    _ if road_oneway.into() => Direction::Forward,
+                    // A contraflow bicycle lane
+                    _ => Direction::Backward,
+                },
+            });
+            Ok(Self(match (forward, backward) {
+                (Some(forward), Some(backward)) => Location::Both { forward, backward },
+                (Some(forward), None) => Location::Forward(forward),
+                (None, Some(backward)) => Location::Backward(backward),
+                (None, None) => Location::None,
+            }))
         }
     }
 }
 
+/// Contraflow cycleway on a oneway road using the deprecated `opposite*` values,
+/// `cycleway=opposite*` or `cycleway:<side>=opposite*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:cycleway#Cycle_lanes>.
+fn opposite(
+    tags: &TrackedTags,
+    locale: &Locale,
+    road_oneway: Oneway,
+    warnings: &mut RoadWarnings,
+) -> Option<Location> {
+    const OPPOSITE: [&str; 3] = ["opposite", "opposite_lane", "opposite_track"];
+    let forward = CYCLEWAY + locale.driving_side.tag();
+    let backward = CYCLEWAY + locale.driving_side.opposite().tag();
+    let (key, value) = [CYCLEWAY, forward.clone(), backward.clone()]
+        .into_iter()
+        .find_map(|key| {
+            tags.get(&key)
+                .filter(|value| OPPOSITE.contains(value))
+                .map(|value| (key, value))
+        })?;
+    let subset = tags.subset(&[key.clone(), TagKey::from("oneway")]);
+    // cycleway=opposite oneway=yes oneway:bicycle=no is the accepted form
+    if !(key.as_str() == CYCLEWAY.as_str()
+        && value == "opposite"
+        && bool::from(road_oneway)
+        && tags.is("oneway:bicycle", "no"))
+    {
+        warnings.push(TagsToLanesMsg::deprecated_tags(subset.clone()));
+    }
+    if !bool::from(road_oneway) {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "opposite cycleway on a road that is not oneway",
+            subset,
+        ));
+    }
+    let way = Way {
+        variant: if value == "opposite_track" {
+            Variant::Track
+        } else {
+            Variant::Lane
+        },
+        direction: Direction::Backward,
+    };
+    // The cycleway on the other side, if any, runs with the traffic,
+    // rather than being another contraflow lane from `oneway:bicycle=no`
+    let (other_key, other_side): (TagKey, WaySide) = if key.as_str() == forward.as_str() {
+        (backward, locale.driving_side.opposite().into())
+    } else {
+        (forward.clone(), locale.driving_side.into())
+    };
+    let other = if tags
+        .get(&other_key)
+        .map_or(false, |value| OPPOSITE.contains(&value))
+    {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[key.clone(), other_key]),
+        ));
+        None
+    } else {
+        tags.cycleway_variant(Some(other_side))
+            .ok()
+            .flatten()
+            .map(|variant| Way {
+                variant,
+                direction: Direction::Forward,
+            })
+    };
+    Some(match (key.as_str() == forward.as_str(), other) {
+        (true, None) => Location::Forward(way),
+        (false, None) => Location::Backward(way),
+        (true, Some(other)) => Location::Both {
+            forward: way,
+            backward: other,
+        },
+        (false, Some(other)) => Location::Both {
+            forward: other,
+            backward: way,
+        },
+    })
+}
+
+/// Whether motor traffic may enter a cycle lane,
+/// `cycleway:<side>:lane=advisory` or `cycleway:<side>:lane=exclusive`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:cycleway:lane>.
+/// Unknown values are warned about and default to [`Config::unknown_cycle_lane_advisory`].
+fn lane_change(
+    tags: &TrackedTags,
+    side: &TagKey,
+    config: &Config,
+    warnings: &mut RoadWarnings,
+) -> Infer<LaneChange> {
+    let found = [
+        CYCLEWAY + side.clone() + "lane",
+        CYCLEWAY + "both" + "lane",
+        CYCLEWAY + "lane",
+    ]
+    .into_iter()
+    .find_map(|key| tags.get(&key).map(|value| (key, value)));
+    match found {
+        None => Infer::None,
+        Some((_, "advisory")) => Infer::Direct(LaneChange::Permitted),
+        Some((_, "exclusive")) => Infer::Direct(LaneChange::Prohibited),
+        Some((key, value)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            Infer::Default(if config.unknown_cycle_lane_advisory {
+                LaneChange::Permitted
+            } else {
+                LaneChange::Prohibited
+            })
+        },
+    }
+}
+
+/// Physical separation from the lane inside,
+/// `cycleway:<side>:separation=*` or `cycleway:both:separation=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Proposed_features/cycleway:separation>.
+fn separation(
+    tags: &TrackedTags,
+    locale: &Locale,
+    side: &TagKey,
+    warnings: &mut RoadWarnings,
+) -> Option<(Metre, Style)> {
+    let found = [
+        CYCLEWAY + side.clone() + "separation",
+        CYCLEWAY + "both" + "separation",
+    ]
+    .into_iter()
+    .find_map(|key| tags.get(&key).map(|value| (key, value)));
+    match found {
+        None | Some((_, "no")) => None,
+        Some((_, "kerb")) => Some((locale.separator_widths.kerb, Style::KerbUp)),
+        Some((_, "hatched_area")) => Some((HATCHED_AREA_WIDTH, Style::NoFill)),
+        Some((key, value)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            None
+        },
+    }
+}
+
+/// Colour of the paint of the separation,
+/// `cycleway:<side>:separation:colour=*` or `cycleway:both:separation:colour=*`
+fn separation_colour(
+    tags: &TrackedTags,
+    side: &TagKey,
+    warnings: &mut RoadWarnings,
+) -> Option<Color> {
+    let found = [
+        CYCLEWAY + side.clone() + "separation" + "colour",
+        CYCLEWAY + "both" + "separation" + "colour",
+    ]
+    .into_iter()
+    .find_map(|key| tags.get(&key).map(|value| (key, value)));
+    match found {
+        None => None,
+        Some((_, "white")) => Some(Color::White),
+        Some((_, "yellow")) => Some(Color::Yellow),
+        Some((_, "red")) => Some(Color::Red),
+        Some((_, "green")) => Some(Color::Green),
+        Some((key, value)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            None
+        },
+    }
+}
+
 impl LaneBuilder {
     fn cycle_forward(_locale: &Locale) -> Self {
         Self {
@@ -247,28 +380,50 @@ impl LaneBuilder {
 }
 
 pub(in crate::transform::tags_to_lanes) fn bicycle(
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
+    config: &Config,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
     let scheme = Scheme::from_tags(tags, locale, road.oneway, warnings)?;
-    let lane = |way: Way| match way.direction {
-        Direction::Forward => LaneBuilder::cycle_forward(locale),
-        Direction::Backward => LaneBuilder::cycle_backward(locale),
-        Direction::Both => LaneBuilder::cycle_both(locale),
+    let road_oneway = bool::from(road.oneway);
+    let mut lane = |way: Way, side: TagKey| {
+        let mut lane = match way.direction {
+            Direction::Forward => LaneBuilder::cycle_forward(locale),
+            Direction::Backward => LaneBuilder::cycle_backward(locale),
+            Direction::Both => LaneBuilder::cycle_both(locale),
+        };
+        let cycleway = Cycleway::from(way.variant);
+        lane.cycleway = Infer::Direct(cycleway);
+        lane.width.target = Infer::Default(locale.bicycle_width(cycleway));
+        if way.variant == Variant::Lane {
+            lane.lane_change = lane_change(tags, &side, config, warnings);
+        }
+        // A contraflow track on a oneway road is kept apart from the motor traffic
+        if road_oneway && way.variant == Variant::Track && way.direction == Direction::Backward {
+            lane.buffer = Infer::Default(CONTRAFLOW_TRACK_BUFFER);
+        }
+        if let Some((width, style)) = separation(tags, locale, &side, warnings) {
+            lane.buffer = Infer::Direct(width);
+            lane.buffer_style = Infer::Direct(style);
+            lane.buffer_color = Infer::direct(separation_colour(tags, &side, warnings));
+        }
+        lane
     };
+    let forward_side = locale.driving_side.tag();
+    let backward_side = locale.driving_side.opposite().tag();
     match scheme.0 {
         Location::None | Location::_No => {},
         Location::Forward(way) => {
-            road.push_forward_outside(lane(way));
+            road.push_forward_outside(lane(way, forward_side));
         },
         Location::Backward(way) => {
-            road.push_backward_outside(lane(way));
+            road.push_backward_outside(lane(way, backward_side));
         },
         Location::Both { forward, backward } => {
-            road.push_forward_outside(lane(forward));
-            road.push_backward_outside(lane(backward));
+            road.push_forward_outside(lane(forward, forward_side));
+            road.push_backward_outside(lane(backward, backward_side));
         },
     }
     Ok(())
@@ -277,17 +432,166 @@ pub(in crate::transform::tags_to_lanes) fn bicycle(
 #[cfg(test)]
 mod tests {
     use super::Scheme;
-    use crate::locale::Locale;
-    use crate::road::Direction;
-    use crate::tag::Tags;
+    use crate::locale::{DrivingSide, Locale};
+    use crate::road::{Color, Cycleway, Designated, Direction, Lane, Style};
+    use crate::tag::{Tags, TrackedTags};
     use crate::transform::tags_to_lanes::modes::bicycle::{Location, Variant, Way};
     use crate::transform::tags_to_lanes::oneway::Oneway;
-    use crate::transform::RoadWarnings;
+    use crate::transform::{
+        lanes_to_tags, tags_to_lanes, LanesToTagsConfig, RoadWarnings, TagsToLanesConfig,
+        TransformResult,
+    };
+
+    #[test]
+    fn opposite_on_driving_side() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "1"],
+            ["cycleway:right", "opposite_track"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        assert!(road_from_tags.road.lanes.iter().any(|lane| matches!(
+            lane,
+            Lane::Travel {
+                direction: Some(Direction::Backward),
+                designated: Designated::Bicycle,
+                ..
+            }
+        )));
+        // deprecated tag
+        assert!(!road_from_tags.warnings.is_empty());
+    }
+
+    #[test]
+    fn opposite_lane_on_both_sides() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["oneway:bicycle", "no"],
+            ["cycleway:left", "opposite_lane"],
+            ["cycleway:right", "opposite_lane"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        let directions: Vec<Option<Direction>> = road_from_tags
+            .road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    direction,
+                    designated: Designated::Bicycle,
+                    ..
+                } => Some(*direction),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(directions, vec![Some(Direction::Backward)]);
+        assert!(TransformResult::from(road_from_tags)
+            .codes
+            .contains(&"ambiguous"));
+    }
+
+    #[test]
+    fn lane_and_track_widths() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let width = |variant: &str| {
+            let tags =
+                Tags::from_str_pairs(&[["highway", "residential"], ["cycleway:right", variant]])
+                    .unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road
+                .lanes
+                .into_iter()
+                .find_map(|lane| match lane {
+                    Lane::Travel {
+                        designated: Designated::Bicycle,
+                        width: Some(width),
+                        cycleway: Some(cycleway),
+                        ..
+                    } => Some((width, cycleway)),
+                    _ => None,
+                })
+                .unwrap()
+        };
+        assert_eq!(
+            width("lane"),
+            (locale.bicycle_width(Cycleway::Lane), Cycleway::Lane)
+        );
+        assert_eq!(
+            width("track"),
+            (locale.bicycle_width(Cycleway::Track), Cycleway::Track)
+        );
+        assert!(width("lane").0.val() < width("track").0.val());
+    }
+
+    #[test]
+    fn coloured_buffer() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["cycleway:right", "lane"],
+            ["cycleway:right:separation", "hatched_area"],
+            ["cycleway:right:separation:colour", "green"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert!(road.lanes.iter().any(|lane| matches!(
+            lane,
+            Lane::Separator { markings }
+                if markings.iter().any(|marking| {
+                    marking.style == Style::NoFill && marking.color == Some(Color::Green)
+                })
+        )));
+    }
+
+    #[test]
+    fn oneway_contraflow_track() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "1"],
+            ["cycleway:left", "track"],
+            ["cycleway:left:oneway", "-1"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default()).unwrap();
+        let lanes = road_from_tags.road.lanes;
+        assert!(matches!(
+            lanes.first(),
+            Some(Lane::Travel {
+                direction: Some(Direction::Backward),
+                designated: Designated::Bicycle,
+                ..
+            })
+        ));
+        match lanes.get(1) {
+            Some(Lane::Separator { markings }) => {
+                assert_eq!(markings.len(), 3);
+                assert_eq!(
+                    markings.get(1).and_then(|buffer| buffer.width),
+                    Some(super::CONTRAFLOW_TRACK_BUFFER)
+                );
+            },
+            lane => panic!("expected buffer, got {:?}", lane),
+        }
+        assert!(lanes.get(2).unwrap().is_motor());
+    }
 
     #[test]
     fn cycleway_lane() {
         let scheme = Scheme::from_tags(
-            &Tags::from_str_pair(["cycleway", "lane"]),
+            &TrackedTags::new(&Tags::from_str_pair(["cycleway", "lane"])),
             &Locale::builder().build(),
             Oneway::No,
             &mut RoadWarnings::default(),
@@ -312,7 +616,9 @@ mod tests {
     #[ignore]
     fn err_cycleway_1() {
         let scheme = Scheme::from_tags(
-            &Tags::from_str_pairs(&[["cycleway", "no"], ["cycleway:left", "lane"]]).unwrap(),
+            &TrackedTags::new(
+                &Tags::from_str_pairs(&[["cycleway", "no"], ["cycleway:left", "lane"]]).unwrap(),
+            ),
             &Locale::builder().build(),
             Oneway::No,
             &mut RoadWarnings::default(),
@@ -324,7 +630,9 @@ mod tests {
     #[ignore]
     fn err_cycleway_2() {
         let scheme = Scheme::from_tags(
-            &Tags::from_str_pairs(&[["cycleway", "track"], ["cycleway:left", "no"]]).unwrap(),
+            &TrackedTags::new(
+                &Tags::from_str_pairs(&[["cycleway", "track"], ["cycleway:left", "no"]]).unwrap(),
+            ),
             &Locale::builder().build(),
             Oneway::No,
             &mut RoadWarnings::default(),
@@ -336,12 +644,160 @@ mod tests {
     #[ignore]
     fn err_cycleway_3() {
         let scheme = Scheme::from_tags(
-            &Tags::from_str_pairs(&[["cycleway:both", "lane"], ["cycleway:right", "track"]])
-                .unwrap(),
+            &TrackedTags::new(
+                &Tags::from_str_pairs(&[["cycleway:both", "lane"], ["cycleway:right", "track"]])
+                    .unwrap(),
+            ),
             &Locale::builder().build(),
             Oneway::No,
             &mut RoadWarnings::default(),
         );
         assert!(scheme.is_err())
     }
+
+    #[test]
+    fn unknown_lane_value() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "1"],
+            ["cycleway:right", "lane"],
+            ["cycleway:right:lane", "pictogram"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let dashed = |unknown_cycle_lane_advisory: bool| {
+            let config = TagsToLanesConfig {
+                unknown_cycle_lane_advisory,
+                ..TagsToLanesConfig::default()
+            };
+            let road_from_tags = tags_to_lanes(&tags, &locale, &config).unwrap();
+            assert!(!road_from_tags.warnings.is_empty());
+            road_from_tags.road.lanes.iter().any(|lane| {
+                matches!(
+                    lane,
+                    Lane::Separator { markings }
+                        if markings.iter().any(|marking| marking.style == Style::DashedLine)
+                )
+            })
+        };
+        assert!(dashed(true));
+        assert!(!dashed(false));
+    }
+
+    #[test]
+    fn advisory_lane_roundtrip() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["cycleway:right", "lane"],
+            ["cycleway:right:lane", "advisory"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert!(road.lanes.iter().any(|lane| matches!(
+            lane,
+            Lane::Separator { markings }
+                if markings.iter().any(|marking| marking.style == Style::DashedLine)
+        )));
+        let output_tags = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(output_tags.get("cycleway:right:lane"), Some("advisory"));
+        let output_road = tags_to_lanes(&output_tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(output_road, road);
+    }
+
+    #[test]
+    fn exclusive_lane_not_written() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["cycleway:right", "lane"],
+            ["cycleway:right:lane", "exclusive"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        // The solid line is also inferred without cycleway:right:lane=exclusive
+        let output_tags = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(output_tags.get("cycleway:right:lane"), None);
+        let output_road = tags_to_lanes(&output_tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(output_road, road);
+    }
+
+    #[test]
+    fn asymmetric_sides_oneway() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["oneway:bicycle", "no"],
+            ["cycleway:left", "track"],
+            ["cycleway:right", "lane"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+            .unwrap()
+            .road;
+        let directions: Vec<Option<Direction>> = road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    designated: Designated::Bicycle,
+                    direction,
+                    ..
+                } => Some(*direction),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            directions,
+            vec![Some(Direction::Backward), Some(Direction::Forward)]
+        );
+        // The contraflow track on the left is buffered from the motor traffic
+        match road.lanes.get(1) {
+            Some(Lane::Separator { markings }) => assert_eq!(markings.len(), 3),
+            lane => panic!("expected buffer, got {:?}", lane),
+        }
+    }
+
+    #[test]
+    fn both_separation_kerb() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["cycleway:both", "track"],
+            ["cycleway:both:separation", "kerb"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default()).unwrap();
+        let lanes = &road_from_tags.road.lanes;
+        // The separators inside of the track on each side are kerbs
+        for separator in [lanes.get(1), lanes.iter().rev().nth(1)] {
+            match separator {
+                Some(Lane::Separator { markings }) => {
+                    let styles: Vec<Style> = markings.iter().map(|marking| marking.style).collect();
+                    assert_eq!(styles, vec![Style::KerbUp]);
+                },
+                lane => panic!("expected kerb, got {:?}", lane),
+            }
+        }
+        let output_tags = lanes_to_tags(
+            &road_from_tags.road,
+            &locale,
+            &LanesToTagsConfig::new(false),
+        )
+        .unwrap();
+        assert_eq!(output_tags.get("cycleway:left:separation"), Some("kerb"));
+        assert_eq!(output_tags.get("cycleway:right:separation"), Some("kerb"));
+    }
 }
@@ -1,6 +1,6 @@
 use crate::locale::Locale;
 use crate::road::Direction;
-use crate::tag::{TagKey, Tags};
+use crate::tag::{TagKey, TrackedTags};
 use crate::transform::tags_to_lanes::{Infer, Oneway, RoadBuilder};
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
 
@@ -41,7 +41,7 @@ enum Lane {
     Opposite,
 }
 
-impl Tags {
+impl TrackedTags<'_> {
     fn get_bus_lane<T>(&self, key: T, warnings: &mut RoadWarnings) -> Lane
     where
         T: AsRef<str>,
@@ -62,7 +62,7 @@ impl Tags {
 impl Scheme {
     #[allow(clippy::unnecessary_wraps)]
     pub(in crate::transform::tags_to_lanes) fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         road_oneway: Oneway,
         warnings: &mut RoadWarnings,
@@ -96,6 +96,14 @@ impl Scheme {
         let busway_both: Lane = tags.get_bus_lane(BUSWAY + "both", warnings);
         let busway_both: Variant = match busway_both {
             Lane::None => Variant::None,
+            // Which side of a oneway road would be the contraflow bus lane is unclear
+            Lane::Lane if bool::from(bus_oneway) => {
+                return Err(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
+                    BUSWAY + "both",
+                    ONEWAY,
+                    ONEWAY + "bus",
+                ])));
+            },
             Lane::Lane => Variant::Both,
             Lane::Opposite => {
                 warnings.push(TagsToLanesMsg::unsupported_tags(
@@ -157,7 +165,7 @@ impl Scheme {
 }
 
 pub(in crate::transform::tags_to_lanes) fn busway(
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
@@ -1,9 +1,9 @@
 use crate::locale::Locale;
-use crate::road::Designated;
-use crate::tag::{TagKey, Tags};
+use crate::road::{BusBay, Designated, Direction};
+use crate::tag::{TagKey, TrackedTags};
 use crate::transform::tags_to_lanes::access_by_lane::Access;
 use crate::transform::tags_to_lanes::{
-    Infer, LaneBuilder, LaneBuilderError, Oneway, RoadBuilder, TagsToLanesMsg,
+    Infer, LaneBuilder, LaneBuilderError, LaneType, Oneway, RoadBuilder, TagsToLanesMsg,
 };
 use crate::transform::RoadWarnings;
 
@@ -11,11 +11,34 @@ mod busway;
 use busway::{busway, Scheme as BuswayScheme};
 
 const LANES: TagKey = TagKey::from("lanes");
+const BUS_BAY: TagKey = TagKey::from("bus_bay");
+const ACCESS_LANES: TagKey = TagKey::from("access:lanes");
+
+/// Keys read by [`bus`] and [`bus_bay`]
+pub(super) const KEYS: &[&str] = &[
+    "busway",
+    "busway:*",
+    "oneway:bus",
+    "lanes:bus",
+    "lanes:bus:*",
+    "lanes:psv",
+    "lanes:psv:*",
+    "bus:lanes",
+    "bus:lanes:*",
+    "psv:lanes",
+    "psv:lanes:*",
+    "access:lanes",
+    "access:lanes:*",
+    "bus_bay",
+];
 
 impl LaneBuilder {
     #[allow(clippy::unnecessary_wraps)]
-    fn set_bus(&mut self, _locale: &Locale) -> Result<(), LaneBuilderError> {
+    fn set_bus(&mut self, locale: &Locale) -> Result<(), LaneBuilderError> {
         self.designated = Infer::Direct(Designated::Bus);
+        if !matches!(self.width.target, Infer::Direct(_)) {
+            self.width.target = Infer::Default(locale.bus_width());
+        }
         Ok(())
     }
 }
@@ -35,7 +58,7 @@ pub struct BusLaneCount {
 impl BusLaneCount {
     #[allow(clippy::unnecessary_wraps)]
     pub(in crate::transform::tags_to_lanes) fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         oneway: Oneway,
         warnings: &mut RoadWarnings,
@@ -53,7 +76,7 @@ impl BusLaneCount {
 
 #[allow(clippy::unnecessary_wraps)]
 pub(in crate::transform::tags_to_lanes) fn bus(
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
@@ -61,14 +84,12 @@ pub(in crate::transform::tags_to_lanes) fn bus(
     // https://wiki.openstreetmap.org/wiki/Bus_lanes
     // 3 schemes, for simplicity we only allow one at a time
     match (
-        tags.tree().get("busway").is_some(),
-        tags.tree()
-            .get("lanes:bus")
-            .or_else(|| tags.tree().get("lanes:psv"))
+        tags.tree_get("busway").is_some(),
+        tags.tree_get("lanes:bus")
+            .or_else(|| tags.tree_get("lanes:psv"))
             .is_some(),
-        tags.tree()
-            .get("bus:lanes")
-            .or_else(|| tags.tree().get("psv:lanes"))
+        tags.tree_get("bus:lanes")
+            .or_else(|| tags.tree_get("psv:lanes"))
             .is_some(),
     ) {
         (false, false, false) => {},
@@ -83,12 +104,60 @@ pub(in crate::transform::tags_to_lanes) fn bus(
         },
     }
 
+    bus_lanes_conditional(tags, locale, road, warnings)?;
+
+    Ok(())
+}
+
+/// Bus lanes that only apply some of the time, e.g. at peak hours,
+/// `bus:lanes:conditional=|designated @ (Mo-Fr 07:00-09:00)`,
+/// see <https://wiki.openstreetmap.org/wiki/Conditional_restrictions>
+fn bus_lanes_conditional(
+    tags: &TrackedTags,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let (key, value) = match [
+        TagKey::from("bus:lanes:conditional"),
+        TagKey::from("psv:lanes:conditional"),
+    ]
+    .into_iter()
+    .find_map(|key| tags.get(&key).map(|value| (key, value)))
+    {
+        Some(found) => found,
+        None => return Ok(()),
+    };
+    let tokens: Vec<&str> = value.split('|').collect();
+    let is_travel = |lane: &&mut LaneBuilder| lane.r#type.some() == Some(LaneType::Travel);
+    if road.lanes_ltr_mut(locale).filter(is_travel).count() != tokens.len() {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "lane count mismatch",
+            tags.subset(&[key]),
+        ));
+        return Ok(());
+    }
+    for (lane, token) in road.lanes_ltr_mut(locale).filter(is_travel).zip(tokens) {
+        if token.is_empty() {
+            continue;
+        }
+        match token
+            .split_once('@')
+            .map(|(access, _condition)| access.trim())
+        {
+            Some("designated") => {
+                lane.set_bus(locale)?;
+                lane.conditional = Infer::Direct(token.trim().to_owned());
+            },
+            _ => warnings.push(TagsToLanesMsg::unsupported_tag(key.clone(), value)),
+        }
+    }
     Ok(())
 }
 
 #[allow(clippy::unnecessary_wraps)]
 fn lanes_bus(
-    tags: &Tags,
+    tags: &TrackedTags,
     _locale: &Locale,
     _road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
@@ -109,10 +178,10 @@ fn lanes_bus(
 }
 
 fn bus_lanes(
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     road: &mut RoadBuilder,
-    _warnings: &mut RoadWarnings,
+    warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
     match (
         tags.get("bus:lanes"),
@@ -147,11 +216,8 @@ fn bus_lanes(
                     ]),
                 ));
             }
-            for (lane, access) in road.lanes_ltr_mut(locale).zip(access.iter()) {
-                if let Access::Designated = access {
-                    lane.set_bus(locale)?;
-                }
-            }
+            check_closed_lanes(tags, &ACCESS_LANES, &access, warnings);
+            set_bus_lanes(road.lanes_ltr_mut(locale), &access, false, locale)?;
         },
         // lanes:bus:forward and lanes:bus:backward, or lanes:psv:forward and lanes:psv:backward
         (None, (forward, backward), None, (None, None))
@@ -163,11 +229,8 @@ fn bus_lanes(
                         tags.subset(&["bus:lanes:backward", "psv:lanes:backward"]),
                     )
                 })?;
-                for (lane, access) in road.forward_ltr_mut(locale).zip(forward_access.iter()) {
-                    if let Access::Designated = access {
-                        lane.set_bus(locale)?;
-                    }
-                }
+                check_closed_lanes(tags, &(ACCESS_LANES + "forward"), &forward_access, warnings);
+                set_bus_lanes(road.forward_ltr_mut(locale), &forward_access, true, locale)?;
             }
             if let Some(backward) = backward {
                 let backward_access = Access::split(backward).map_err(|a| {
@@ -176,11 +239,18 @@ fn bus_lanes(
                         tags.subset(&["bus:lanes:backward", "psv:lanes:backward"]),
                     )
                 })?;
-                for (lane, access) in road.backward_ltr_mut(locale).zip(backward_access.iter()) {
-                    if let Access::Designated = access {
-                        lane.set_bus(locale)?;
-                    }
-                }
+                check_closed_lanes(
+                    tags,
+                    &(ACCESS_LANES + "backward"),
+                    &backward_access,
+                    warnings,
+                );
+                set_bus_lanes(
+                    road.backward_ltr_mut(locale),
+                    &backward_access,
+                    true,
+                    locale,
+                )?;
             }
         },
         // Don't try to understand this
@@ -206,3 +276,305 @@ fn bus_lanes(
 
     Ok(())
 }
+
+/// Warn about lanes designated for buses that `access:lanes=*` closes with `no`,
+/// comparing the tokens of the two schemes for the same lanes
+fn check_closed_lanes(
+    tags: &TrackedTags,
+    access_key: &TagKey,
+    bus_access: &[Access],
+    warnings: &mut RoadWarnings,
+) {
+    let access = match tags.get(access_key).map(Access::split) {
+        None => return,
+        Some(Ok(access)) => access,
+        Some(Err(a)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(access_key.clone(), &a));
+            return;
+        },
+    };
+    if access
+        .iter()
+        .zip(bus_access)
+        .any(|pair| matches!(pair, (Access::No, Access::Designated)))
+    {
+        warnings.push(TagsToLanesMsg::ambiguous_str(
+            "lane designated by bus:lanes=* is closed by access:lanes=*",
+        ));
+    }
+}
+
+/// Match `*:lanes` tokens to the lanes left to right.
+/// Lanes that are not travel lanes have no token.
+/// Both direction lanes, e.g. a centre turn lane, have no token in the directional
+/// `*:lanes:{forward,backward}` schemes, wherever they are.
+fn set_bus_lanes<'a>(
+    lanes: impl Iterator<Item = &'a mut LaneBuilder>,
+    access: &[Access],
+    directional: bool,
+    locale: &Locale,
+) -> Result<(), TagsToLanesMsg> {
+    let lanes = lanes.filter(|lane| {
+        lane.r#type.some() == Some(LaneType::Travel)
+            && !(directional && lane.direction.some() == Some(Direction::Both))
+    });
+    for (lane, access) in lanes.zip(access.iter()) {
+        if let Access::Designated = access {
+            lane.set_bus(locale)?;
+        }
+    }
+    Ok(())
+}
+
+/// Pull-in bus stops, <https://wiki.openstreetmap.org/wiki/Key:bus_bay>
+// TODO: model the widened section as an extra lane
+pub(in crate::transform::tags_to_lanes) fn bus_bay(
+    tags: &TrackedTags,
+    _locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Option<BusBay> {
+    match tags.get(BUS_BAY) {
+        None | Some("no") => None,
+        Some("left") => Some(BusBay::Left),
+        Some("right") => Some(BusBay::Right),
+        Some("both") => Some(BusBay::Both),
+        Some(v) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(BUS_BAY, v));
+            None
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::{BusBay, Designated, Direction, Lane};
+    use crate::tag::{HighwayImportance, HighwayType, Tags};
+    use crate::transform::{lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    fn designated(tags: &Tags) -> Vec<(Option<Direction>, Designated)> {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        tags_to_lanes(tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road
+            .lanes
+            .into_iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    direction,
+                    designated,
+                    ..
+                } => Some((direction, designated)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bus_lanes_conditional() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["bus:lanes:conditional", "|designated @ (Mo-Fr 07:00-09:00)"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        assert!(road_from_tags.warnings.is_empty());
+        let conditional: Vec<(Designated, Option<String>)> = road_from_tags
+            .road
+            .lanes
+            .into_iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel {
+                    designated,
+                    conditional,
+                    ..
+                } => Some((designated, conditional)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            conditional,
+            vec![
+                (Designated::Motor, None),
+                (
+                    Designated::Bus,
+                    Some(String::from("designated @ (Mo-Fr 07:00-09:00)"))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn bus_lane_closed_by_access_lanes() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let warnings = |access_lanes: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["oneway", "yes"],
+                ["lanes", "2"],
+                ["bus:lanes", "|designated"],
+                ["access:lanes", access_lanes],
+            ])
+            .unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+                .unwrap()
+                .warnings
+        };
+        assert!(warnings("yes|yes").is_empty());
+        assert!(!warnings("yes|no").is_empty());
+    }
+
+    #[test]
+    fn bus_width() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let widths = |tags: &[[&str; 2]]| -> Vec<(Designated, Option<Metre>)> {
+            let tags = Tags::from_str_pairs(tags).unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+                .unwrap()
+                .road
+                .lanes
+                .into_iter()
+                .filter_map(|lane| match lane {
+                    Lane::Travel {
+                        designated, width, ..
+                    } => Some((designated, width)),
+                    _ => None,
+                })
+                .collect()
+        };
+        let motor = locale.travel_width(
+            &Designated::Motor,
+            HighwayType::Classified(HighwayImportance::Primary),
+        );
+        let bus = locale.bus_width();
+        assert!(bus.val() > motor.val());
+        let (motor, bus) = (Some(motor), Some(bus));
+        assert_eq!(
+            widths(&[["highway", "busway"]]),
+            vec![(Designated::Bus, bus), (Designated::Bus, bus)]
+        );
+        assert_eq!(
+            widths(&[
+                ["highway", "primary"],
+                ["oneway", "yes"],
+                ["lanes", "2"],
+                ["busway", "lane"],
+            ]),
+            vec![(Designated::Motor, motor), (Designated::Bus, bus)]
+        );
+    }
+
+    #[test]
+    fn bus_lanes_centre_turn_lane() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "primary"],
+            ["lanes", "5"],
+            ["lanes:forward", "2"],
+            ["lanes:backward", "2"],
+            ["lanes:both_ways", "1"],
+            ["bus:lanes", "designated||||designated"],
+        ])
+        .unwrap();
+        assert_eq!(
+            designated(&tags),
+            vec![
+                (Some(Direction::Backward), Designated::Bus),
+                (Some(Direction::Backward), Designated::Motor),
+                (Some(Direction::Both), Designated::Motor),
+                (Some(Direction::Forward), Designated::Motor),
+                (Some(Direction::Forward), Designated::Bus),
+            ]
+        );
+    }
+
+    #[test]
+    fn bus_lanes_forward_centre_turn_lane() {
+        // the centre turn lane is the first of the forward lanes, but has no token
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "primary"],
+            ["lanes", "5"],
+            ["lanes:forward", "2"],
+            ["lanes:backward", "2"],
+            ["lanes:both_ways", "1"],
+            ["bus:lanes:forward", "|designated"],
+            ["bus:lanes:backward", "|designated"],
+        ])
+        .unwrap();
+        assert_eq!(
+            designated(&tags),
+            vec![
+                (Some(Direction::Backward), Designated::Bus),
+                (Some(Direction::Backward), Designated::Motor),
+                (Some(Direction::Both), Designated::Motor),
+                (Some(Direction::Forward), Designated::Motor),
+                (Some(Direction::Forward), Designated::Bus),
+            ]
+        );
+    }
+
+    #[test]
+    fn bus_bay_right() {
+        let tags = Tags::from_str_pairs(&[["highway", "primary"], ["bus_bay", "right"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(road.bus_bay, Some(BusBay::Right));
+    }
+
+    #[test]
+    fn busway_both() {
+        let two_way = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["lanes", "4"],
+            ["busway:both", "lane"],
+        ])
+        .unwrap();
+        assert_eq!(
+            designated(&two_way),
+            vec![
+                (Some(Direction::Backward), Designated::Bus),
+                (Some(Direction::Backward), Designated::Motor),
+                (Some(Direction::Forward), Designated::Motor),
+                (Some(Direction::Forward), Designated::Bus),
+            ]
+        );
+        let oneway = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "3"],
+            ["busway:both", "lane"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        assert!(tags_to_lanes(&oneway, &locale, &TagsToLanesConfig::default()).is_err());
+    }
+
+    #[test]
+    fn highway_busway_two_way() {
+        let tags =
+            Tags::from_str_pairs(&[["highway", "busway"], ["lanes", "2"], ["bus", "yes"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let config = TagsToLanesConfig::new(true, false);
+        let road = tags_to_lanes(&tags, &locale, &config).unwrap().road;
+        assert_eq!(
+            designated(&tags),
+            vec![
+                (Some(Direction::Backward), Designated::Bus),
+                (Some(Direction::Forward), Designated::Bus),
+            ]
+        );
+        let roundtrip_tags = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip_tags.get("highway"), Some("busway"));
+        assert_eq!(roundtrip_tags.get("oneway"), None);
+        let roundtrip = tags_to_lanes(&roundtrip_tags, &locale, &config)
+            .unwrap()
+            .road;
+        assert_eq!(roundtrip.lanes, road.lanes);
+    }
+}
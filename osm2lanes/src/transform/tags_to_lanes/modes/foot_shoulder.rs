@@ -3,12 +3,29 @@ use celes::Country;
 use crate::locale::Locale;
 use crate::metric::Metre;
 use crate::road::Designated;
-use crate::tag::Tags;
+use crate::tag::{
+    Access as AccessValue, HighwayImportance, HighwayType, TagKey, TrackedTags, HIGHWAY,
+};
 use crate::transform::tags::{SHOULDER, SIDEWALK};
 use crate::transform::tags_to_lanes::road::Width;
 use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, RoadBuilder, TagsToLanesMsg};
 use crate::transform::{RoadError, RoadWarnings};
 
+/// Keys read by [`foot_and_shoulder`]
+pub(super) const KEYS: &[&str] = &[
+    "sidewalk",
+    "sidewalk:*",
+    "sidewalk:bicycle",
+    "sidewalk:*:bicycle",
+    "sidewalk:width",
+    "sidewalk:*:width",
+    "shoulder",
+    "shoulder:surface",
+    "shoulder:*:surface",
+    "shoulder:access:*",
+    "shoulder:*:access:*",
+];
+
 impl LaneBuilder {
     fn shoulder(locale: &Locale) -> Self {
         let mut shoulder = Self {
@@ -32,11 +49,85 @@ impl LaneBuilder {
             ..Default::default()
         }
     }
+    /// Bicycle access of a sidewalk on the given side,
+    /// from `sidewalk:<side>:bicycle=*`
+    fn set_sidewalk_details(
+        &mut self,
+        tags: &TrackedTags,
+        side: &TagKey,
+        warnings: &mut RoadWarnings,
+    ) -> Result<(), TagsToLanesMsg> {
+        if let Some(value) = [
+            SIDEWALK + side.clone() + "bicycle",
+            SIDEWALK + "both" + "bicycle",
+            SIDEWALK + "bicycle",
+        ]
+        .into_iter()
+        .find(|key| tags.get(key).is_some())
+        .and_then(|key| tags.get_parsed::<_, AccessValue>(key, warnings))
+        {
+            self.access.bicycle.set(Infer::Direct(value))?;
+        }
+        Ok(())
+    }
+    /// Width of a sidewalk on the given side, from `sidewalk:<side>:width=*`
+    fn set_sidewalk_width(
+        &mut self,
+        tags: &TrackedTags,
+        side: &TagKey,
+        warnings: &mut RoadWarnings,
+    ) {
+        if let Some(width) = [
+            SIDEWALK + side.clone() + "width",
+            SIDEWALK + "both" + "width",
+            SIDEWALK + "width",
+        ]
+        .into_iter()
+        .find(|key| tags.get(key).is_some())
+        .and_then(|key| tags.get_parsed::<_, f64>(key, warnings))
+        {
+            self.width.target = Infer::Direct(Metre::new(width));
+        }
+    }
     fn is_bicycle(&self) -> bool {
         self.designated.some() == Some(Designated::Bicycle)
     }
+    /// Surface and access of a shoulder on the given side,
+    /// from `shoulder:<side>:surface=*` and `shoulder:<side>:access:<mode>=*`
+    fn set_shoulder_details(
+        &mut self,
+        tags: &TrackedTags,
+        side: &TagKey,
+        warnings: &mut RoadWarnings,
+    ) -> Result<(), TagsToLanesMsg> {
+        let key = |suffix: &'static str| -> Option<TagKey> {
+            [
+                SHOULDER + side.clone() + suffix,
+                SHOULDER + "both" + suffix,
+                SHOULDER + suffix,
+            ]
+            .into_iter()
+            .find(|key| tags.get(key).is_some())
+        };
+        if let Some(surface) = key("surface").and_then(|key| tags.get(key)) {
+            self.surface = Infer::Direct(surface.to_owned());
+        }
+        for (suffix, access) in [
+            ("access:foot", &mut self.access.foot),
+            ("access:bicycle", &mut self.access.bicycle),
+            ("access:motor_vehicle", &mut self.access.motor),
+        ] {
+            if let Some(value) =
+                key(suffix).and_then(|key| tags.get_parsed::<_, AccessValue>(key, warnings))
+            {
+                access.set(Infer::Direct(value))?;
+            }
+        }
+        Ok(())
+    }
 }
 
+#[derive(Clone, Copy, PartialEq)]
 enum Sidewalk {
     None,
     No,
@@ -50,10 +141,12 @@ impl Sidewalk {
     /// None is when information may be incomplete and should be inferred,
     /// e.g. when sidewalk=* is missing altogether,
     /// but this may later become a No when combined with data from shoulder=*
-    /// We catch any tag combinations that violate the OSM spec
+    /// We catch any tag combinations that violate the OSM spec,
+    /// except for `sidewalk:<side>=*` contradicting `sidewalk=*` or `sidewalk:both=*`,
+    /// where the side wins with a warning.
     #[allow(clippy::unnested_or_patterns)]
     fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         warnings: &mut RoadWarnings,
     ) -> Result<(Self, Self), TagsToLanesMsg> {
@@ -110,6 +203,32 @@ impl Sidewalk {
                     return err;
                 },
             },
+            // sidewalk= or sidewalk:both= with sidewalk:left= and/or sidewalk:right=
+            (Some(_), None, (forward, backward)) | (None, Some(_), (forward, backward)) => {
+                let general = Self::from_tags(
+                    &tags.subset(&[SIDEWALK, SIDEWALK + "both"]),
+                    locale,
+                    warnings,
+                )?;
+                let side = |value: Option<&str>, fallback: Sidewalk| match value {
+                    None => Some(fallback),
+                    Some("yes") => Some(Sidewalk::Yes),
+                    Some("no") => Some(Sidewalk::No),
+                    Some("separate") => Some(Sidewalk::Separate),
+                    Some(_) => None,
+                };
+                match (side(forward, general.0), side(backward, general.1)) {
+                    (Some(forward), Some(backward)) => {
+                        if (forward, backward) != general {
+                            warnings.push(TagsToLanesMsg::ambiguous_str(
+                                "sidewalk:<side>=* contradicts sidewalk=*, using sidewalk:<side>=*",
+                            ));
+                        }
+                        (forward, backward)
+                    },
+                    _ => return err,
+                }
+            },
             (Some(_), Some(_), (_, _))
             | (Some(_), _, (_, Some(_)) | (Some(_), _))
             | (_, Some(_), (_, Some(_)) | (Some(_), _)) => {
@@ -128,7 +247,7 @@ enum Shoulder {
 
 impl Shoulder {
     fn from_tags(
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         _warnings: &mut RoadWarnings,
     ) -> Result<(Self, Self), TagsToLanesMsg> {
@@ -147,7 +266,7 @@ impl Shoulder {
 
 #[allow(clippy::items_after_statements, clippy::unnested_or_patterns)]
 pub(in crate::transform::tags_to_lanes) fn foot_and_shoulder(
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
@@ -155,6 +274,19 @@ pub(in crate::transform::tags_to_lanes) fn foot_and_shoulder(
     // https://wiki.openstreetmap.org/wiki/Key:sidewalk
     let sidewalk: (Sidewalk, Sidewalk) = Sidewalk::from_tags(tags, locale, warnings)?;
 
+    // Motorways have no sidewalks, so `sidewalk=no` is redundant but a sidewalk contradicts
+    if road.highway.r#type() == HighwayType::Classified(HighwayImportance::Motorway)
+        && (sidewalk.0 == Sidewalk::Yes || sidewalk.1 == Sidewalk::Yes)
+    {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[
+            HIGHWAY,
+            SIDEWALK,
+            SIDEWALK + "both",
+            SIDEWALK + "left",
+            SIDEWALK + "right",
+        ])));
+    }
+
     // https://wiki.openstreetmap.org/wiki/Key:shoulder
     let shoulder: (Shoulder, Shoulder) = Shoulder::from_tags(tags, locale, warnings)?;
 
@@ -177,9 +309,15 @@ pub(in crate::transform::tags_to_lanes) fn foot_and_shoulder(
             &mut self,
             (sidewalk, shoulder): (Sidewalk, Shoulder),
             forward: bool,
-            tags: &Tags,
+            tags: &TrackedTags,
             locale: &Locale,
+            warnings: &mut RoadWarnings,
         ) -> Result<(), RoadError> {
+            let side = if forward {
+                locale.driving_side.tag()
+            } else {
+                locale.driving_side.opposite().tag()
+            };
             match (sidewalk, shoulder) {
                 (Sidewalk::No | Sidewalk::None, Shoulder::None) => {
                     // We assume a shoulder if there is no bike lane.
@@ -191,22 +329,34 @@ pub(in crate::transform::tags_to_lanes) fn foot_and_shoulder(
                         && locale.has_shoulder(self.highway.r#type())
                         && (forward || !bool::from(self.oneway))
                     {
-                        self.push_outside(LaneBuilder::shoulder(locale), forward);
+                        let mut shoulder = LaneBuilder::shoulder(locale);
+                        shoulder.set_shoulder_details(tags, &side, warnings)?;
+                        // The shoulder stands in for a sidewalk that has only its width tagged
+                        shoulder.set_sidewalk_width(tags, &side, warnings);
+                        self.push_outside(shoulder, forward);
                     }
                 },
                 (Sidewalk::No | Sidewalk::None, Shoulder::No) => {},
                 (Sidewalk::Yes, Shoulder::No | Shoulder::None) => {
-                    self.push_outside(LaneBuilder::foot(locale), forward);
+                    let mut foot = LaneBuilder::foot(locale);
+                    foot.set_sidewalk_details(tags, &side, warnings)?;
+                    foot.set_sidewalk_width(tags, &side, warnings);
+                    self.push_outside(foot, forward);
                 },
                 (Sidewalk::No | Sidewalk::None, Shoulder::Yes) => {
-                    self.push_outside(LaneBuilder::shoulder(locale), forward);
+                    let mut shoulder = LaneBuilder::shoulder(locale);
+                    shoulder.set_shoulder_details(tags, &side, warnings)?;
+                    self.push_outside(shoulder, forward);
                 },
                 (Sidewalk::Yes, Shoulder::Yes) => {
-                    return Err(TagsToLanesMsg::unsupported(
-                        "shoulder and sidewalk on same side",
-                        tags.subset(&[SIDEWALK, SHOULDER]),
-                    )
-                    .into());
+                    // Both explicitly tagged, the shoulder is inside of the sidewalk
+                    let mut shoulder = LaneBuilder::shoulder(locale);
+                    shoulder.set_shoulder_details(tags, &side, warnings)?;
+                    self.push_outside(shoulder, forward);
+                    let mut foot = LaneBuilder::foot(locale);
+                    foot.set_sidewalk_details(tags, &side, warnings)?;
+                    foot.set_sidewalk_width(tags, &side, warnings);
+                    self.push_outside(foot, forward);
                 },
                 (Sidewalk::Separate, _) => {
                     return Err(TagsToLanesMsg::unsupported_tag(SIDEWALK, "separate").into())
@@ -216,8 +366,81 @@ pub(in crate::transform::tags_to_lanes) fn foot_and_shoulder(
         }
     }
 
-    road.add_sidewalk_shoulder((sidewalk.0, shoulder.0), true, tags, locale)?;
-    road.add_sidewalk_shoulder((sidewalk.1, shoulder.1), false, tags, locale)?;
+    road.add_sidewalk_shoulder((sidewalk.0, shoulder.0), true, tags, locale, warnings)?;
+    road.add_sidewalk_shoulder((sidewalk.1, shoulder.1), false, tags, locale, warnings)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::Lane;
+    use crate::tag::{Access, Tags};
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn shoulder_surface_and_access() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "primary"],
+            ["shoulder", "right"],
+            ["shoulder:right:surface", "asphalt"],
+            ["shoulder:right:access:bicycle", "yes"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        match road.lanes.last() {
+            Some(Lane::Shoulder {
+                surface,
+                access: Some(access),
+                ..
+            }) => {
+                assert_eq!(surface.as_deref(), Some("asphalt"));
+                assert_eq!(access.bicycle, Some(Access::Yes));
+            },
+            lane => panic!("expected shoulder, got {:?}", lane),
+        }
+    }
+
+    #[test]
+    fn sidewalk_bicycle_access() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["sidewalk", "both"],
+            ["sidewalk:left:bicycle", "yes"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        let bicycle_access = |lane: Option<&Lane>| match lane {
+            Some(Lane::Travel { access, .. }) => access.as_ref().and_then(|a| a.bicycle),
+            lane => panic!("expected sidewalk, got {:?}", lane),
+        };
+        assert_eq!(bicycle_access(road.lanes.first()), Some(Access::Yes));
+        assert_eq!(bicycle_access(road.lanes.last()), None);
+    }
+
+    #[test]
+    fn sidewalk_width_on_inferred_shoulder() {
+        let tags = Tags::from_str_pairs(&[["highway", "primary"], ["sidewalk:right:width", "1.5"]])
+            .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        match road.lanes.last() {
+            Some(Lane::Shoulder { width, .. }) => assert_eq!(*width, Some(Metre::new(1.5))),
+            lane => panic!("expected shoulder, got {:?}", lane),
+        }
+        assert!(matches!(
+            road.lanes.first(),
+            Some(Lane::Shoulder { width: None, .. })
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::locale::Locale;
+use crate::tag::{Access as AccessValue, TagKey, TrackedTags};
+use crate::transform::tags_to_lanes::access_by_lane::Access;
+use crate::transform::tags_to_lanes::road::LaneType;
+use crate::transform::tags_to_lanes::{Infer, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+const HGV_LANES: TagKey = TagKey::from("hgv:lanes");
+const LANES: TagKey = TagKey::from("lanes");
+const MAXWEIGHT: TagKey = TagKey::from("maxweight");
+
+/// Keys read by [`hgv`] and [`max_weight`]
+pub(super) const KEYS: &[&str] = &["hgv:lanes", "maxweight"];
+
+/// Heavy goods vehicle access of each lane from `hgv:lanes=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:hgv>.
+/// The tokens are matched to the travel lanes left to right,
+/// so this comes before any cycle lanes are added.
+pub(in crate::transform::tags_to_lanes) fn hgv(
+    tags: &TrackedTags,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let access = match tags.get(HGV_LANES).map(Access::split) {
+        None => return Ok(()),
+        Some(Ok(access)) => access,
+        Some(Err(a)) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(HGV_LANES, &a));
+            return Ok(());
+        },
+    };
+    if access.len() != road.len() {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[HGV_LANES, LANES]),
+        ));
+        return Ok(());
+    }
+    let lanes = road
+        .lanes_ltr_mut(locale)
+        .filter(|lane| lane.r#type.some() == Some(LaneType::Travel));
+    for (lane, access) in lanes.zip(access) {
+        let value = match access {
+            Access::None => continue,
+            Access::No => AccessValue::No,
+            Access::Yes => AccessValue::Yes,
+            Access::Designated => AccessValue::Designated,
+        };
+        lane.access.hgv.set(Infer::Direct(value))?;
+    }
+    Ok(())
+}
+
+/// Weight limit in tonnes from `maxweight=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:maxweight>
+pub(in crate::transform::tags_to_lanes) fn max_weight(
+    tags: &TrackedTags,
+    warnings: &mut RoadWarnings,
+) -> Option<f64> {
+    let value = tags.get(MAXWEIGHT)?;
+    match value.strip_suffix(" t").unwrap_or(value).parse::<f64>() {
+        Ok(tonnes) => Some(tonnes),
+        Err(_) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(MAXWEIGHT, value));
+            None
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Speed;
+    use crate::road::Lane;
+    use crate::tag::{Access, Tags};
+    use crate::transform::{lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    #[test]
+    fn hgv_lanes_inner_lane() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["hgv:lanes", "no|yes"],
+            ["maxweight", "7.5"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(true, false))
+            .unwrap()
+            .road;
+        let hgv: Vec<Option<Access>> = road
+            .lanes
+            .iter()
+            .filter(|lane| lane.is_motor())
+            .map(|lane| match lane {
+                Lane::Travel { access, .. } => access.as_ref().and_then(|a| a.hgv.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(hgv, vec![Some(Access::No), Some(Access::Yes)]);
+        assert_eq!(road.max_weight, Some(7.5));
+
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("hgv:lanes"), Some("no|yes"));
+        assert_eq!(roundtrip.get("maxweight"), Some("7.5"));
+    }
+
+    #[test]
+    fn max_speed_hgv() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "primary"],
+            ["maxspeed", "100"],
+            ["maxspeed:hgv", "60"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        assert_eq!(road.max_speed_hgv, Some(Speed::Kph(60.0)));
+        assert_eq!(road.max_speed_bus, None);
+
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("maxspeed:hgv"), Some("60"));
+    }
+}
@@ -0,0 +1,415 @@
+use crate::locale::Locale;
+use crate::metric::{Speed, SpeedError};
+use crate::road::{Designated, Direction};
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::tags_to_lanes::road::LaneType;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+/// Keys read by [`max_speed`] and [`vehicle_max_speed`]
+pub(super) const KEYS: &[&str] = &[
+    "maxspeed",
+    "maxspeed:lanes",
+    "maxspeed:lanes:forward",
+    "maxspeed:lanes:backward",
+    "maxspeed:type",
+    "source:maxspeed",
+    "maxspeed:hgv",
+    "maxspeed:bus",
+];
+
+const MAXSPEED: TagKey = TagKey::from("maxspeed");
+
+/// Speed limits of the motor and bus lanes, see <https://wiki.openstreetmap.org/wiki/Key:maxspeed>.
+/// `maxspeed=*` is the default for every lane, overridden per lane by `maxspeed:lanes=*`,
+/// left to right, or by `maxspeed:lanes:forward=*` and `maxspeed:lanes:backward=*`,
+/// left to right in the direction of travel.
+pub(in crate::transform::tags_to_lanes) fn max_speed(
+    tags: &TrackedTags,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    let max_speed = match tags.get(MAXSPEED) {
+        None => None,
+        // A pipe in maxspeed=* is a mistake for maxspeed:lanes=*
+        Some(value) if value.contains('|') => {
+            warnings.push(TagsToLanesMsg::ambiguous_tag(MAXSPEED, value));
+            let lanes = road.lanes_ltr_mut(locale);
+            set_lanes(tags, MAXSPEED, None, lanes, false, locale, warnings);
+            None
+        },
+        Some(value) => match Speed::from_tag_value(value, locale) {
+            Ok(max_speed) => Some(max_speed),
+            Err(_e) => {
+                warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[MAXSPEED])));
+                None
+            },
+        },
+    };
+    maxspeed_source(tags, max_speed, warnings);
+
+    if let Some(max_speed) = max_speed {
+        for lane in road
+            .lanes_ltr_mut(locale)
+            .filter(|lane| is_carriageway(lane, false))
+        {
+            lane.max_speed = Infer::Default(max_speed);
+        }
+    }
+    set_lanes(
+        tags,
+        MAXSPEED + "lanes",
+        max_speed,
+        road.lanes_ltr_mut(locale),
+        false,
+        locale,
+        warnings,
+    );
+    set_lanes(
+        tags,
+        MAXSPEED + "lanes" + "forward",
+        max_speed,
+        road.forward_ltr_mut(locale),
+        true,
+        locale,
+        warnings,
+    );
+    set_lanes(
+        tags,
+        MAXSPEED + "lanes" + "backward",
+        max_speed,
+        road.backward_ltr_mut(locale),
+        true,
+        locale,
+        warnings,
+    );
+}
+
+/// Speed limit for one kind of vehicle, from `maxspeed:<vehicle>=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:maxspeed#Maxspeed_for_vehicle_types>
+pub(in crate::transform::tags_to_lanes) fn vehicle_max_speed(
+    tags: &TrackedTags,
+    vehicle: &'static str,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Option<Speed> {
+    speed(tags, MAXSPEED + vehicle, locale, warnings)
+}
+
+/// The speed of a `maxspeed=*` like key, see [`Speed::from_tag_value`],
+/// warning about any value that is not a speed
+pub(in crate::transform::tags_to_lanes) fn speed(
+    tags: &TrackedTags,
+    key: TagKey,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Option<Speed> {
+    let value = tags.get(&key)?;
+    match Speed::from_tag_value(value, locale) {
+        Ok(speed) => Some(speed),
+        Err(_e) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            None
+        },
+    }
+}
+
+/// How the speed limit is known, from `maxspeed:type=*` or `source:maxspeed=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:source:maxspeed>.
+/// A posted sign, `sign`, is the limit in `maxspeed=*`.
+/// Other values are zones, e.g. `DE:urban`, which only need to be understood without `maxspeed=*`.
+fn maxspeed_source(tags: &TrackedTags, max_speed: Option<Speed>, warnings: &mut RoadWarnings) {
+    for key in ["maxspeed:type", "source:maxspeed"] {
+        match (tags.get(key), max_speed) {
+            (None, _) | (Some(_), Some(_)) => {},
+            (Some("sign"), None) => {
+                warnings.push(TagsToLanesMsg::ambiguous_tags(
+                    tags.subset(&[key, "maxspeed"]),
+                ));
+            },
+            (Some(_), None) => {
+                warnings.push(TagsToLanesMsg::unimplemented(
+                    "maxspeed zone",
+                    tags.subset(&[key]),
+                ));
+            },
+        }
+    }
+}
+
+/// A motor or bus lane, leaving out both direction lanes for the directional schemes
+fn is_carriageway(lane: &LaneBuilder, directional: bool) -> bool {
+    lane.r#type.some() == Some(LaneType::Travel)
+        && matches!(
+            lane.designated.some(),
+            Some(Designated::Motor | Designated::Bus)
+        )
+        && !(directional && lane.direction.some() == Some(Direction::Both))
+}
+
+/// Match the `|` separated speeds to the motor and bus lanes.
+/// An empty segment or `none` leaves the lane without a speed limit.
+/// A value without a segment for each lane is ignored with a warning.
+fn set_lanes<'a>(
+    tags: &TrackedTags,
+    key: TagKey,
+    max_speed: Option<Speed>,
+    lanes: impl Iterator<Item = &'a mut LaneBuilder>,
+    directional: bool,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) {
+    let value = match tags.get(&key) {
+        Some(value) => value,
+        None => return,
+    };
+    let speeds = match value
+        .split('|')
+        .map(|speed| match speed {
+            "" | "none" => Ok(Infer::None),
+            speed => Speed::from_tag_value(speed, locale).map(Infer::Direct),
+        })
+        .collect::<Result<Vec<Infer<Speed>>, SpeedError>>()
+    {
+        Ok(speeds) => speeds,
+        Err(_e) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            return;
+        },
+    };
+    let lanes: Vec<&mut LaneBuilder> = lanes
+        .filter(|lane| is_carriageway(lane, directional))
+        .collect();
+    if speeds.len() != lanes.len() {
+        warnings.push(TagsToLanesMsg::unsupported(
+            &format!("{} does not match the lane count", key.as_str()),
+            tags.subset(&[key]),
+        ));
+        return;
+    }
+    // A lane faster than the limit for the whole way is suspicious
+    if max_speed.map_or(false, |max_speed| {
+        speeds
+            .iter()
+            .any(|speed| matches!(speed, Infer::Direct(speed) if *speed > max_speed))
+    }) {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[MAXSPEED, key]),
+        ));
+    }
+    for (lane, speed) in lanes.into_iter().zip(speeds) {
+        lane.max_speed = speed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Speed;
+    use crate::road::Lane;
+    use crate::tag::Tags;
+    use crate::transform::{lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    /// Speed limits of the motor lanes left to right, and whether there were no warnings
+    fn max_speeds(tags: &[[&str; 2]]) -> (Vec<Option<Speed>>, bool) {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = tags_to_lanes(
+            &Tags::from_str_pairs(tags).unwrap(),
+            &locale,
+            &TagsToLanesConfig::new(false, false),
+        )
+        .unwrap();
+        let max_speeds = road_from_tags
+            .road
+            .lanes
+            .iter()
+            .filter(|lane| lane.is_motor())
+            .map(|lane| match lane {
+                Lane::Travel { max_speed, .. } => *max_speed,
+                _ => unreachable!(),
+            })
+            .collect();
+        (max_speeds, road_from_tags.warnings.is_empty())
+    }
+
+    #[test]
+    fn maxspeed_lanes_empty_and_none() {
+        let (max_speeds, ok) = max_speeds(&[
+            ["highway", "primary"],
+            ["oneway", "yes"],
+            ["lanes", "3"],
+            ["maxspeed", "100"],
+            ["maxspeed:lanes", "|none|80"],
+        ]);
+        assert!(ok);
+        assert_eq!(max_speeds, vec![None, None, Some(Speed::Kph(80.0))]);
+    }
+
+    #[test]
+    fn maxspeed_lanes_directional() {
+        let (max_speeds, ok) = max_speeds(&[
+            ["highway", "primary"],
+            ["lanes", "3"],
+            ["lanes:forward", "2"],
+            ["lanes:backward", "1"],
+            ["maxspeed", "60"],
+            ["maxspeed:lanes:forward", "60|40"],
+            ["maxspeed:lanes:backward", "50"],
+        ]);
+        assert!(ok);
+        assert_eq!(
+            max_speeds,
+            vec![
+                Some(Speed::Kph(50.0)),
+                Some(Speed::Kph(60.0)),
+                Some(Speed::Kph(40.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn maxspeed_lanes_count_mismatch() {
+        let (max_speeds, ok) = max_speeds(&[
+            ["highway", "primary"],
+            ["oneway", "yes"],
+            ["lanes", "2"],
+            ["maxspeed", "60"],
+            ["maxspeed:lanes", "60|50|40"],
+        ]);
+        assert!(!ok);
+        assert_eq!(max_speeds, vec![Some(Speed::Kph(60.0)); 2]);
+    }
+
+    #[test]
+    fn maxspeed_lanes_roundtrip() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        for (tags, written) in [
+            (
+                vec![
+                    ["highway", "primary"],
+                    ["oneway", "yes"],
+                    ["lanes", "3"],
+                    ["maxspeed", "100"],
+                    ["maxspeed:lanes", "|none|80"],
+                ],
+                vec![["maxspeed", "80"], ["maxspeed:lanes", "none|none|80"]],
+            ),
+            (
+                vec![
+                    ["highway", "primary"],
+                    ["lanes", "3"],
+                    ["lanes:forward", "2"],
+                    ["lanes:backward", "1"],
+                    ["maxspeed", "60"],
+                    ["maxspeed:lanes:forward", "60|40"],
+                    ["maxspeed:lanes:backward", "50"],
+                ],
+                vec![
+                    ["maxspeed", "60"],
+                    ["maxspeed:lanes:forward", "60|40"],
+                    ["maxspeed:lanes:backward", "50"],
+                ],
+            ),
+        ] {
+            let road = tags_to_lanes(
+                &Tags::from_str_pairs(&tags).unwrap(),
+                &locale,
+                &TagsToLanesConfig::new(false, false),
+            )
+            .unwrap()
+            .road;
+            let tags = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+            for [key, value] in written {
+                assert_eq!(tags.get(key), Some(value), "{}", key);
+            }
+            let roundtrip =
+                tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+            assert!(roundtrip.warnings.is_empty());
+            assert_eq!(roundtrip.road, road);
+        }
+    }
+
+    #[test]
+    fn maxspeed_walk_and_locale_unit() {
+        let (max_speeds, ok) = max_speeds(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["maxspeed", "walk"],
+        ]);
+        assert!(ok);
+        assert_eq!(max_speeds, vec![Some(Speed::WALK)]);
+
+        let locale = Locale::builder()
+            .driving_side(DrivingSide::Right)
+            .iso_3166("US")
+            .build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "residential"],
+            ["oneway", "yes"],
+            ["maxspeed", "25"],
+            ["maxspeed:hgv", "20"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(true, false))
+            .unwrap()
+            .road;
+        assert!(road.lanes.iter().any(|lane| matches!(
+            lane,
+            Lane::Travel {
+                max_speed: Some(Speed::Mph(max_speed)),
+                ..
+            } if (*max_speed - 25.0).abs() < f64::EPSILON
+        )));
+        assert_eq!(road.max_speed_hgv, Some(Speed::Mph(20.0)));
+    }
+
+    #[test]
+    fn maxspeed_roundtrip_locale_unit() {
+        let usa = Locale::builder()
+            .driving_side(DrivingSide::Right)
+            .iso_3166("US")
+            .build();
+        let germany = Locale::builder()
+            .driving_side(DrivingSide::Right)
+            .iso_3166("DE")
+            .build();
+        for (locale, tags) in [
+            (
+                &usa,
+                vec![
+                    ["highway", "primary"],
+                    ["oneway", "yes"],
+                    ["lanes", "2"],
+                    ["maxspeed", "25"],
+                    ["maxspeed:lanes", "25|20"],
+                    ["maxspeed:hgv", "20"],
+                ],
+            ),
+            (
+                &germany,
+                vec![
+                    ["highway", "residential"],
+                    ["oneway", "yes"],
+                    ["maxspeed", "walk"],
+                ],
+            ),
+        ] {
+            let road = tags_to_lanes(
+                &Tags::from_str_pairs(&tags).unwrap(),
+                locale,
+                &TagsToLanesConfig::new(false, false),
+            )
+            .unwrap()
+            .road;
+            let written = lanes_to_tags(&road, locale, &LanesToTagsConfig::new(false)).unwrap();
+            for [key, value] in tags.iter().filter(|[key, _]| key.starts_with("maxspeed")) {
+                assert_eq!(written.get(*key), Some(*value), "{}", key);
+            }
+            let roundtrip =
+                tags_to_lanes(&written, locale, &TagsToLanesConfig::new(false, false)).unwrap();
+            assert!(roundtrip.warnings.is_empty());
+            assert_eq!(roundtrip.road, road);
+        }
+    }
+}
@@ -1,10 +1,19 @@
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::{RoadWarnings, TagsToLanesMsg};
+
 /// Modes of travel
 ///
 mod bicycle;
 pub(super) use bicycle::bicycle;
 
 mod bus;
-pub(super) use bus::{bus, BusLaneCount};
+pub(super) use bus::{bus, bus_bay, BusLaneCount};
+
+mod hgv;
+pub(super) use hgv::{hgv, max_weight};
+
+mod max_speed;
+pub(super) use max_speed::{max_speed, speed, vehicle_max_speed};
 
 mod foot_shoulder;
 pub(super) use foot_shoulder::foot_and_shoulder;
@@ -13,4 +22,36 @@ mod parking;
 pub(super) use parking::parking;
 
 mod non_motorized;
-pub(super) use non_motorized::non_motorized;
+pub(super) use non_motorized::{is_area, non_motorized};
+
+mod turns;
+pub(super) use turns::turns;
+
+mod rail;
+pub(super) use rail::rail;
+
+/// Keys read by the modes, see [`super::recognized_tag_keys`]
+pub(super) const KEYS: &[&[&str]] = &[
+    bicycle::KEYS,
+    bus::KEYS,
+    hgv::KEYS,
+    foot_shoulder::KEYS,
+    parking::KEYS,
+    non_motorized::KEYS,
+    turns::KEYS,
+    max_speed::KEYS,
+    rail::KEYS,
+];
+
+/// `yes` or `no`, warning about any other value
+fn yes_no(tags: &TrackedTags, key: TagKey, warnings: &mut RoadWarnings) -> Option<bool> {
+    match tags.get(&key) {
+        None => None,
+        Some("yes") => Some(true),
+        Some("no") => Some(false),
+        Some(value) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            None
+        },
+    }
+}
@@ -1,12 +1,79 @@
+use super::yes_no;
 use crate::locale::Locale;
-use crate::road::{Designated, Direction};
-use crate::tag::{Access, Tags, HIGHWAY};
-use crate::transform::tags_to_lanes::{RoadBuilder, TagsToLanesMsg};
+use crate::road::{Designated, Direction, Footway, Steps};
+use crate::tag::{Access, TagKey, TrackedTags, HIGHWAY};
+use crate::transform::tags_to_lanes::road::LaneType;
+use crate::transform::tags_to_lanes::{LaneBuilder, RoadBuilder, TagsToLanesMsg};
 use crate::transform::{Infer, RoadWarnings};
 
+const AREA: TagKey = TagKey::from("area");
+const BICYCLE: TagKey = TagKey::from("bicycle");
+const FOOT: TagKey = TagKey::from("foot");
+const FOOTWAY: TagKey = TagKey::from("footway");
+const HORSE: TagKey = TagKey::from("horse");
+const LANES: TagKey = TagKey::from("lanes");
+const SEGREGATED: TagKey = TagKey::from("segregated");
+const STEP_COUNT: TagKey = TagKey::from("step_count");
+const HANDRAIL: TagKey = TagKey::from("handrail");
+const RAMP: TagKey = TagKey::from("ramp");
+
+/// Keys read by [`non_motorized`]
+pub(super) const KEYS: &[&str] = &[
+    "area",
+    "bicycle",
+    "foot",
+    "footway",
+    "horse",
+    "segregated",
+    "step_count",
+    "handrail",
+    "ramp",
+];
+
+/// Lanes of a `highway=path`
+#[derive(Debug, PartialEq)]
+enum Path {
+    /// A footpath, the default
+    Foot,
+    /// A cycle path, `bicycle=designated`
+    Bicycle,
+    /// A path shared by foot and bicycle, `bicycle=designated` + `foot=designated`
+    Shared,
+    /// A footpath beside a cycle path, as shared with `segregated=yes`
+    Segregated,
+}
+
+impl Path {
+    /// <https://wiki.openstreetmap.org/wiki/Tag:highway%3Dpath>
+    ///
+    /// | `bicycle=`   | `foot=`      | `segregated=` | path       |
+    /// |--------------|--------------|---------------|------------|
+    /// | `designated` | `designated` | `yes`         | segregated |
+    /// | `designated` | `designated` | other         | shared     |
+    /// | `designated` | other        |               | bicycle    |
+    /// | other        |              |               | foot       |
+    fn from_tags(tags: &TrackedTags) -> Self {
+        match (
+            tags.is(BICYCLE, "designated"),
+            tags.is(FOOT, "designated"),
+            tags.is(SEGREGATED, "yes"),
+        ) {
+            (true, true, true) => Self::Segregated,
+            (true, true, false) => Self::Shared,
+            (true, false, _) => Self::Bicycle,
+            (false, _, _) => Self::Foot,
+        }
+    }
+}
+
+/// A plaza, `highway=pedestrian` + `area=yes`, rather than a linear way
+pub(in crate::transform::tags_to_lanes) fn is_area(tags: &TrackedTags) -> bool {
+    tags.is(HIGHWAY, "pedestrian") && tags.is(AREA, "yes")
+}
+
 #[allow(clippy::unnecessary_wraps, clippy::restriction)]
 pub(in crate::transform::tags_to_lanes) fn non_motorized(
-    tags: &Tags,
+    tags: &TrackedTags,
     _locale: &Locale,
     road: &mut RoadBuilder,
     warnings: &mut RoadWarnings,
@@ -17,22 +84,316 @@ pub(in crate::transform::tags_to_lanes) fn non_motorized(
         log::trace!("motorized");
         return Ok(());
     }
+    // A plaza is a single foot lane covering the whole area
+    if is_area(tags) {
+        if road.len() != 1 {
+            warnings.push(TagsToLanesMsg::unsupported(
+                "pedestrian area with lanes",
+                tags.subset(&[HIGHWAY, AREA, LANES]),
+            ));
+            return Ok(());
+        }
+        if let Some(lane) = road.forward_outside_mut() {
+            lane.direction.set(Infer::Direct(Direction::Both))?;
+            lane.designated.set(Infer::Direct(Designated::Foot))?;
+            lane.access.foot.set(Infer::Direct(Access::Designated))?;
+            lane.access.motor.set(Infer::Direct(Access::No))?;
+        }
+        return Ok(());
+    }
     // Easy special cases first.
-    if let Some(v @ ("steps" | "path")) = tags.get(HIGHWAY) {
+    if let Some(v @ ("steps" | "path" | "footway" | "cycleway")) = tags.get(HIGHWAY) {
         // TODO: how to avoid making this assumption?
         assert_eq!(road.len(), 1);
+        let path = match v {
+            "path" => Path::from_tags(tags),
+            "cycleway" => Path::Bicycle,
+            _ => Path::Foot,
+        };
+        // Cyclists must walk, so the way is a footway with `bicycle=dismount` access
+        let path = if tags.is(BICYCLE, "dismount") {
+            Path::Foot
+        } else {
+            path
+        };
+        if tags.is(HORSE, "designated") {
+            warnings.push(TagsToLanesMsg::unimplemented(
+                "bridleway",
+                tags.subset(&[HIGHWAY, HORSE]),
+            ));
+        }
+        let foot = tags.get_parsed::<_, Access>(FOOT, warnings);
+        let bicycle = tags.get_parsed::<_, Access>(BICYCLE, warnings);
         let lane = road.forward_outside_mut().unwrap();
-        lane.designated.set(Infer::Direct(Designated::Foot))?;
         lane.direction.set(Infer::Direct(Direction::Both))?;
-        lane.access.foot.set(Infer::Direct(Access::Designated))?;
         lane.access.motor.set(Infer::Direct(Access::No))?;
+        match path {
+            Path::Foot => {
+                lane.designated.set(Infer::Direct(Designated::Foot))?;
+                lane.access.foot.set(Infer::Direct(Access::Designated))?;
+                lane.access.bicycle.set(Infer::direct(bicycle))?;
+            },
+            Path::Bicycle => {
+                lane.designated.set(Infer::Direct(Designated::Bicycle))?;
+                lane.access.bicycle.set(Infer::Direct(Access::Designated))?;
+                lane.access.foot.set(Infer::direct(foot))?;
+            },
+            Path::Shared => {
+                lane.designated.set(Infer::Direct(Designated::Foot))?;
+                lane.access.foot.set(Infer::Direct(Access::Designated))?;
+                lane.access.bicycle.set(Infer::Direct(Access::Designated))?;
+            },
+            Path::Segregated => {
+                lane.designated.set(Infer::Direct(Designated::Bicycle))?;
+                lane.access.bicycle.set(Infer::Direct(Access::Designated))?;
+                lane.access.foot.set(Infer::Direct(Access::No))?;
+                // The side of the footpath is not tagged, assume it is outside
+                road.push_forward_outside(LaneBuilder {
+                    r#type: Infer::Direct(LaneType::Travel),
+                    direction: Infer::Direct(Direction::Both),
+                    designated: Infer::Direct(Designated::Foot),
+                    ..Default::default()
+                });
+                let foot_lane = road.forward_outside_mut().unwrap();
+                foot_lane
+                    .access
+                    .foot
+                    .set(Infer::Direct(Access::Designated))?;
+                foot_lane.access.bicycle.set(Infer::Direct(Access::No))?;
+                foot_lane.access.motor.set(Infer::Direct(Access::No))?;
+            },
+        }
+        if v == "footway" {
+            if let Some(lane) = road.forward_outside_mut() {
+                lane.footway = Infer::direct(tags.get_parsed::<_, Footway>(FOOTWAY, warnings));
+            }
+        }
         if v == "steps" {
-            warnings.push(TagsToLanesMsg::unimplemented(
-                "steps becomes sidewalk",
-                tags.subset(&[HIGHWAY]),
-            ));
+            road.forward_outside_mut().unwrap().steps = Infer::Direct(Steps {
+                step_count: tags.get_parsed(STEP_COUNT, warnings),
+                handrail: yes_no(tags, HANDRAIL, warnings),
+                ramp: yes_no(tags, RAMP, warnings),
+            });
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Path;
+    use crate::locale::{DrivingSide, Locale};
+    use crate::road::{Designated, Footway, Lane, Steps};
+    use crate::tag::{Access, Tags, TrackedTags};
+    use crate::transform::{lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    fn designated(tags: &[[&str; 2]]) -> Vec<Designated> {
+        let tags = Tags::from_str_pairs(tags).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Travel { designated, .. } => Some(*designated),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn path_decision_table() {
+        for (tags, path) in [
+            (vec![], Path::Foot),
+            (vec![["bicycle", "yes"]], Path::Foot),
+            (vec![["bicycle", "designated"]], Path::Bicycle),
+            (
+                vec![["bicycle", "designated"], ["foot", "yes"]],
+                Path::Bicycle,
+            ),
+            (
+                vec![["bicycle", "designated"], ["foot", "designated"]],
+                Path::Shared,
+            ),
+            (
+                vec![
+                    ["bicycle", "designated"],
+                    ["foot", "designated"],
+                    ["segregated", "no"],
+                ],
+                Path::Shared,
+            ),
+            (
+                vec![
+                    ["bicycle", "designated"],
+                    ["foot", "designated"],
+                    ["segregated", "yes"],
+                ],
+                Path::Segregated,
+            ),
+            (vec![["foot", "designated"]], Path::Foot),
+        ] {
+            let tags = Tags::from_str_pairs(&tags).unwrap();
+            assert_eq!(Path::from_tags(&TrackedTags::new(&tags)), path);
+        }
+    }
+
+    #[test]
+    fn path_lanes() {
+        assert_eq!(designated(&[["highway", "path"]]), vec![Designated::Foot]);
+        assert_eq!(
+            designated(&[["highway", "path"], ["bicycle", "designated"]]),
+            vec![Designated::Bicycle]
+        );
+        assert_eq!(
+            designated(&[
+                ["highway", "path"],
+                ["bicycle", "designated"],
+                ["foot", "designated"],
+            ]),
+            vec![Designated::Foot]
+        );
+        assert_eq!(
+            designated(&[
+                ["highway", "path"],
+                ["bicycle", "designated"],
+                ["foot", "designated"],
+                ["segregated", "yes"],
+            ]),
+            vec![Designated::Bicycle, Designated::Foot]
+        );
+    }
+
+    #[test]
+    fn bicycle_dismount() {
+        assert_eq!(
+            designated(&[["highway", "cycleway"]]),
+            vec![Designated::Bicycle]
+        );
+        let tags =
+            Tags::from_str_pairs(&[["highway", "cycleway"], ["bicycle", "dismount"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        assert!(road_from_tags.warnings.is_empty());
+        let road = road_from_tags.road;
+        match road.lanes.as_slice() {
+            [Lane::Travel {
+                designated: Designated::Foot,
+                access: Some(access),
+                ..
+            }] => assert_eq!(access.bicycle, Some(Access::Dismount)),
+            lanes => panic!("expected a footway, got {:?}", lanes),
+        }
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("bicycle"), Some("dismount"));
+    }
+
+    #[test]
+    fn steps() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "steps"],
+            ["step_count", "20"],
+            ["handrail", "yes"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        assert!(road_from_tags.warnings.is_empty());
+        match road_from_tags.road.lanes.as_slice() {
+            [Lane::Travel {
+                designated: Designated::Foot,
+                steps,
+                ..
+            }] => assert_eq!(
+                *steps,
+                Some(Steps {
+                    step_count: Some(20),
+                    handrail: Some(true),
+                    ramp: None,
+                })
+            ),
+            lanes => panic!("expected a footway, got {:?}", lanes),
+        }
+    }
+
+    #[test]
+    fn steps_supported() {
+        // Steps were once unimplemented, with a warning, even without any details
+        let tags = Tags::from_str_pairs(&[["highway", "steps"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(true, false)).unwrap();
+        assert!(matches!(
+            road_from_tags.road.lanes.as_slice(),
+            [Lane::Travel {
+                designated: Designated::Foot,
+                steps: Some(_),
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn footway_kind() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        for (value, footway) in [
+            ("sidewalk", Some(Footway::Sidewalk)),
+            ("crossing", Some(Footway::Crossing)),
+            ("unknown", None),
+        ] {
+            let tags = Tags::from_str_pairs(&[["highway", "footway"], ["footway", value]]).unwrap();
+            let road_from_tags =
+                tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+            assert_eq!(road_from_tags.warnings.is_empty(), footway.is_some());
+            let road = road_from_tags.road;
+            match road.lanes.as_slice() {
+                [Lane::Travel {
+                    designated: Designated::Foot,
+                    footway: actual,
+                    ..
+                }] => assert_eq!(*actual, footway),
+                lanes => panic!("expected a footway, got {:?}", lanes),
+            }
+            let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+            if footway.is_some() {
+                assert_eq!(roundtrip.get("highway"), Some("footway"));
+                assert_eq!(roundtrip.get("footway"), Some(value));
+            } else {
+                assert_eq!(roundtrip.get("highway"), Some("path"));
+            }
+        }
+    }
+
+    #[test]
+    fn pedestrian_area() {
+        let tags = Tags::from_str_pairs(&[["highway", "pedestrian"], ["area", "yes"]]).unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags =
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap();
+        assert!(road_from_tags.warnings.is_empty());
+        let road = road_from_tags.road;
+        assert!(road.is_area);
+        match road.lanes.as_slice() {
+            [Lane::Travel {
+                designated: Designated::Foot,
+                access: Some(access),
+                ..
+            }] => assert_eq!(access.motor, Some(Access::No)),
+            lanes => panic!("expected a single foot area, got {:?}", lanes),
+        }
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("highway"), Some("pedestrian"));
+        assert_eq!(roundtrip.get("area"), Some("yes"));
+
+        // A pedestrian street is still a linear way
+        let tags = Tags::from_str_pairs(&[["highway", "pedestrian"]]).unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false))
+            .unwrap()
+            .road;
+        assert!(!road.is_area);
+    }
+}
@@ -1,8 +1,27 @@
-use crate::locale::Locale;
-use crate::road::{Designated, Direction};
-use crate::tag::Tags;
-use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, RoadBuilder};
-use crate::transform::RoadError;
+use super::yes_no;
+use crate::locale::{DrivingSide, Locale};
+use crate::metric::Metre;
+use crate::road::{Designated, Direction, ParkingOrientation};
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::tags::CYCLEWAY;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, RoadBuilder, TagsToLanesMsg};
+use crate::transform::{RoadError, RoadWarnings};
+
+const PARKING: TagKey = TagKey::from("parking");
+const PARKING_LANE: TagKey = TagKey::from("parking:lane");
+const PARKING_CONDITION: TagKey = TagKey::from("parking:condition");
+
+/// Keys read by [`parking`]
+pub(super) const KEYS: &[&str] = &[
+    "parking:lane:*",
+    "parking:lane:*:conditional",
+    "parking:condition:*",
+    "parking:*:fee",
+    "cycleway:*:separation:*",
+];
+
+/// Space kept clear of opening car doors between parking and a cycle lane
+const DOOR_ZONE: Metre = Metre::new(0.75);
 
 impl LaneBuilder {
     fn parking_forward(_locale: &Locale) -> Self {
@@ -25,20 +44,141 @@ impl LaneBuilder {
 
 #[allow(clippy::unnecessary_wraps)]
 pub(in crate::transform::tags_to_lanes) fn parking(
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
 ) -> Result<(), RoadError> {
+    // Parking lanes are on the outside of the traffic on that side
+    let (forward, backward) = match locale.driving_side {
+        DrivingSide::Right => ("right", "left"),
+        DrivingSide::Left => ("left", "right"),
+    };
     let has_parking = vec!["parallel", "diagonal", "perpendicular"];
-    let parking_lane_fwd = tags.is_any("parking:lane:right", &has_parking)
-        || tags.is_any("parking:lane:both", &has_parking);
-    let parking_lane_back = tags.is_any("parking:lane:left", &has_parking)
-        || tags.is_any("parking:lane:both", &has_parking);
+    let parking_lane_fwd = tags.is_any(PARKING_LANE + forward, &has_parking)
+        || tags.is_any(PARKING_LANE + "both", &has_parking);
+    let parking_lane_back = tags.is_any(PARKING_LANE + backward, &has_parking)
+        || tags.is_any(PARKING_LANE + "both", &has_parking);
+
+    let orientation = |side: &'static str| -> Option<ParkingOrientation> {
+        match tags
+            .get(PARKING_LANE + side)
+            .or_else(|| tags.get(PARKING_LANE + "both"))
+        {
+            Some("parallel") => Some(ParkingOrientation::Parallel),
+            Some("diagonal") => Some(ParkingOrientation::Diagonal),
+            Some("perpendicular") => Some(ParkingOrientation::Perpendicular),
+            _ => None,
+        }
+    };
+
+    // https://wiki.openstreetmap.org/wiki/Key:parking:lane#Conditional_restrictions
+    // The expression is kept as is, we do not interpret opening hours.
+    let conditional = |side: &'static str| -> Option<String> {
+        tags.get(PARKING_LANE + side + "conditional")
+            .or_else(|| tags.get(PARKING_LANE + "both" + "conditional"))
+            .map(ToOwned::to_owned)
+    };
+
+    // https://wiki.openstreetmap.org/wiki/Key:parking:condition
+    // The value is kept as is, e.g. `ticket`, `residents` or `free`.
+    let condition = |side: &'static str| -> Option<String> {
+        tags.get(PARKING_CONDITION + side)
+            .or_else(|| tags.get(PARKING_CONDITION + "both"))
+            .map(ToOwned::to_owned)
+    };
+
+    // `parking:<side>:fee=*` of https://wiki.openstreetmap.org/wiki/Street_parking
+    let fee = |side: &'static str, warnings: &mut RoadWarnings| -> Option<bool> {
+        yes_no(tags, PARKING + side + "fee", warnings)
+            .or_else(|| yes_no(tags, PARKING + "both" + "fee", warnings))
+    };
+
+    // A cycle lane inside of the parking, separated from it by a door zone,
+    // `cycleway:<side>:separation:<side>=parking_lane`
+    let door_zone = |side: &'static str, inside: Option<&LaneBuilder>| -> Infer<Metre> {
+        let cycle_lane = inside.map_or(false, |lane| {
+            lane.designated.some() == Some(Designated::Bicycle)
+        });
+        if cycle_lane && tags.is(CYCLEWAY + side + "separation" + side, "parking_lane") {
+            Infer::Direct(DOOR_ZONE)
+        } else {
+            Infer::None
+        }
+    };
+
     if parking_lane_fwd {
-        road.push_forward_outside(LaneBuilder::parking_forward(locale));
+        let mut lane = LaneBuilder::parking_forward(locale);
+        lane.buffer = door_zone(forward, road.forward_outside());
+        lane.orientation = Infer::direct(orientation(forward));
+        lane.conditional = Infer::direct(conditional(forward));
+        lane.parking_condition = Infer::direct(condition(forward));
+        lane.fee = Infer::direct(fee(forward, warnings));
+        road.push_forward_outside(lane);
+    } else if conditional(forward).is_some() {
+        warnings.push(TagsToLanesMsg::unimplemented(
+            "conditional parking without parking lane",
+            tags.subset(&[
+                PARKING_LANE + forward + "conditional",
+                PARKING_LANE + "both" + "conditional",
+            ]),
+        ));
     }
     if parking_lane_back {
-        road.push_backward_outside(LaneBuilder::parking_backward(locale));
+        let mut lane = LaneBuilder::parking_backward(locale);
+        lane.buffer = door_zone(backward, road.backward_outside());
+        lane.orientation = Infer::direct(orientation(backward));
+        lane.conditional = Infer::direct(conditional(backward));
+        lane.parking_condition = Infer::direct(condition(backward));
+        lane.fee = Infer::direct(fee(backward, warnings));
+        road.push_backward_outside(lane);
+    } else if conditional(backward).is_some() {
+        warnings.push(TagsToLanesMsg::unimplemented(
+            "conditional parking without parking lane",
+            tags.subset(&[
+                PARKING_LANE + backward + "conditional",
+                PARKING_LANE + "both" + "conditional",
+            ]),
+        ));
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::{Designated, Lane, Style};
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn door_zone_beside_cycle_lane() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let door_zone = |separation: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["cycleway:right", "lane"],
+                ["cycleway:right:separation:right", separation],
+                ["parking:lane:right", "parallel"],
+            ])
+            .unwrap();
+            let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+                .unwrap()
+                .road;
+            road.lanes.windows(3).any(|lanes| match lanes {
+                [Lane::Travel {
+                    designated: Designated::Bicycle,
+                    ..
+                }, Lane::Separator { markings }, Lane::Parking { .. }] => {
+                    markings.iter().any(|marking| {
+                        marking.style == Style::NoFill && marking.width == Some(Metre::new(0.75))
+                    })
+                },
+                _ => false,
+            })
+        };
+        assert!(door_zone("parking_lane"));
+        assert!(!door_zone("no"));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::locale::Locale;
+use crate::road::{Designated, Direction};
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, LaneType, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+/// Keys read by [`rail`]
+pub(super) const KEYS: &[&str] = &["railway", "tram", "tram:lanes"];
+
+const RAILWAY: TagKey = TagKey::from("railway");
+const TRAM: TagKey = TagKey::from("tram");
+
+impl LaneBuilder {
+    fn rail(direction: Direction) -> Self {
+        Self {
+            r#type: Infer::Direct(LaneType::Travel),
+            direction: Infer::Direct(direction),
+            designated: Infer::Direct(Designated::Rail),
+            ..Default::default()
+        }
+    }
+}
+
+/// Tram tracks embedded in the carriageway, from `railway=tram` and `tram=yes` on the road,
+/// see <https://wiki.openstreetmap.org/wiki/Tag:railway%3Dtram>.
+/// The tracks are a single lane in the middle of the road, used in both directions
+/// unless the road is oneway.
+pub(in crate::transform::tags_to_lanes) fn rail(
+    tags: &TrackedTags,
+    _locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) {
+    if tags.get(TRAM + "lanes").is_some() {
+        warnings.push(TagsToLanesMsg::unimplemented(
+            "trams sharing lanes",
+            tags.subset(&[TRAM + "lanes"]),
+        ));
+    }
+    if !(tags.is(RAILWAY, "tram") && tags.is(TRAM, "yes")) {
+        return;
+    }
+    let direction = if bool::from(road.oneway) {
+        Direction::Forward
+    } else {
+        Direction::Both
+    };
+    road.push_forward_inside(LaneBuilder::rail(direction));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::road::{Designated, Direction, Lane, Printable, Road};
+    use crate::tag::Tags;
+    use crate::transform::{lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    fn road(tags: &[[&str; 2]], locale: &Locale) -> Road {
+        tags_to_lanes(
+            &Tags::from_str_pairs(tags).unwrap(),
+            locale,
+            &TagsToLanesConfig::new(false, false),
+        )
+        .unwrap()
+        .road
+    }
+
+    /// Glyph and direction of each lane, left to right
+    fn lanes(road: &Road) -> Vec<(char, Option<Direction>)> {
+        road.lanes
+            .iter()
+            .map(|lane| match lane {
+                Lane::Travel { direction, .. } => (lane.as_utf8(), *direction),
+                _ => (lane.as_utf8(), None),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tram_tracks() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = road(
+            &[
+                ["highway", "tertiary"],
+                ["railway", "tram"],
+                ["tram", "yes"],
+            ],
+            &locale,
+        );
+        assert_eq!(
+            lanes(&road),
+            vec![
+                ('🚗', Some(Direction::Backward)),
+                ('🚊', Some(Direction::Both)),
+                ('🚗', Some(Direction::Forward)),
+            ]
+        );
+        let rail = road.lanes.get(1).unwrap();
+        assert_eq!(rail.as_ascii(), 't');
+        assert_eq!(
+            rail.width(&locale, road.highway.r#type()),
+            locale.travel_width(&Designated::Rail, road.highway.r#type())
+        );
+
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(false)).unwrap();
+        assert_eq!(roundtrip.get("railway"), Some("tram"));
+        assert_eq!(roundtrip.get("tram"), Some("yes"));
+        assert_eq!(roundtrip.get("lanes"), Some("2"));
+    }
+
+    #[test]
+    fn tram_tracks_oneway() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = road(
+            &[
+                ["highway", "tertiary"],
+                ["oneway", "yes"],
+                ["railway", "tram"],
+                ["tram", "yes"],
+            ],
+            &locale,
+        );
+        assert_eq!(
+            lanes(&road),
+            vec![
+                ('🚊', Some(Direction::Forward)),
+                ('🚗', Some(Direction::Forward)),
+            ]
+        );
+    }
+
+    #[test]
+    fn railway_without_tram() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = road(&[["highway", "tertiary"], ["railway", "tram"]], &locale);
+        assert!(road.lanes.iter().all(|lane| !matches!(
+            lane,
+            Lane::Travel {
+                designated: Designated::Rail,
+                ..
+            }
+        )));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::locale::Locale;
+use crate::road::{Designated, Direction, TurnDirection};
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::tags_to_lanes::road::LaneType;
+use crate::transform::tags_to_lanes::{Infer, LaneBuilder, RoadBuilder, TagsToLanesMsg};
+use crate::transform::RoadWarnings;
+
+/// Keys read by [`turns`]
+pub(super) const KEYS: &[&str] = &["turn:lanes", "turn:lanes:forward", "turn:lanes:backward"];
+
+const LANES: TagKey = TagKey::from("lanes");
+const TURN_LANES: TagKey = TagKey::from("turn:lanes");
+
+/// Turn markings of the motor and bus lanes from `turn:lanes=*` on oneway roads,
+/// otherwise from `turn:lanes:forward=*` and `turn:lanes:backward=*`,
+/// see <https://wiki.openstreetmap.org/wiki/Key:turn#Turning_indications_per_lane>.
+/// The tokens are matched to the lanes left to right in their direction of travel,
+/// so this comes after any bus and cycle lanes are known.
+pub(in crate::transform::tags_to_lanes) fn turns(
+    tags: &TrackedTags,
+    locale: &Locale,
+    road: &mut RoadBuilder,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    if bool::from(road.oneway) {
+        set_turns(
+            tags,
+            (TURN_LANES, LANES),
+            road.forward_ltr_mut(locale),
+            warnings,
+        )?;
+    } else {
+        set_turns(
+            tags,
+            (TURN_LANES + "forward", LANES + "forward"),
+            road.forward_ltr_mut(locale),
+            warnings,
+        )?;
+        set_turns(
+            tags,
+            (TURN_LANES + "backward", LANES + "backward"),
+            road.backward_ltr_mut(locale),
+            warnings,
+        )?;
+    }
+    Ok(())
+}
+
+/// Match the `|` separated tokens to the motor and bus lanes.
+/// Both direction lanes, e.g. a centre turn lane, have no token.
+/// An empty token leaves the lane without markings,
+/// and a value without a token for each lane, or with an unknown turn, is ignored with a warning.
+/// The number of tokens must also agree with the matching `lanes[:{forward,backward}]=*`,
+/// rather than assigning the turns to the lanes that were inferred otherwise.
+fn set_turns<'a>(
+    tags: &TrackedTags,
+    (key, lanes_key): (TagKey, TagKey),
+    lanes: impl Iterator<Item = &'a mut LaneBuilder>,
+    warnings: &mut RoadWarnings,
+) -> Result<(), TagsToLanesMsg> {
+    let value = match tags.get(&key) {
+        Some(value) => value,
+        None => return Ok(()),
+    };
+    let lanes: Vec<&mut LaneBuilder> = lanes
+        .filter(|lane| {
+            lane.r#type.some() == Some(LaneType::Travel)
+                && matches!(
+                    lane.designated.some(),
+                    Some(Designated::Motor | Designated::Bus)
+                )
+                && lane.direction.some() != Some(Direction::Both)
+        })
+        .collect();
+    let tokens: Vec<&str> = value.split('|').collect();
+    if tags
+        .get(&lanes_key)
+        .and_then(|count| count.parse::<usize>().ok())
+        .map_or(false, |count| count != tokens.len())
+    {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[key, lanes_key]),
+        ));
+        return Ok(());
+    }
+    if tokens.len() != lanes.len() {
+        warnings.push(TagsToLanesMsg::unsupported(
+            "turn:lanes lane count mismatch",
+            tags.subset(&[key]),
+        ));
+        return Ok(());
+    }
+    let turns = match tokens
+        .into_iter()
+        .map(|token| {
+            if token.is_empty() {
+                Ok(None)
+            } else {
+                TurnDirection::parse_lane(token).map(Some)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(turns) => turns,
+        Err(_e) => {
+            warnings.push(TagsToLanesMsg::unsupported_tag(key, value));
+            return Ok(());
+        },
+    };
+    for (lane, turn) in lanes.into_iter().zip(turns) {
+        lane.turn.set(Infer::direct(turn))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::road::Lane;
+    use crate::tag::Tags;
+    use crate::transform::{lanes_to_tags, tags_to_lanes, LanesToTagsConfig, TagsToLanesConfig};
+
+    /// Whether any motor lane has turns, and whether there were no warnings
+    fn turns(tags: &[[&str; 2]]) -> (bool, bool) {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = tags_to_lanes(
+            &Tags::from_str_pairs(tags).unwrap(),
+            &locale,
+            &TagsToLanesConfig::new(false, false),
+        )
+        .unwrap();
+        let turns = road_from_tags
+            .road
+            .lanes
+            .iter()
+            .any(|lane| matches!(lane, Lane::Travel { turn: Some(_), .. }));
+        (turns, road_from_tags.warnings.is_empty())
+    }
+
+    #[test]
+    fn turn_lanes_count_mismatch() {
+        assert_eq!(
+            turns(&[
+                ["highway", "tertiary"],
+                ["oneway", "yes"],
+                ["lanes", "2"],
+                ["turn:lanes", "left|through|right"],
+            ]),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn turn_lanes_forward_count_mismatch() {
+        assert_eq!(
+            turns(&[
+                ["highway", "tertiary"],
+                ["lanes", "3"],
+                ["lanes:forward", "2"],
+                ["lanes:backward", "1"],
+                ["turn:lanes:forward", "left|through|right"],
+            ]),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn turn_lanes_unknown() {
+        assert_eq!(
+            turns(&[
+                ["highway", "tertiary"],
+                ["oneway", "yes"],
+                ["lanes", "2"],
+                ["turn:lanes", "left|sideways"],
+            ]),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn turn_lanes_roundtrip() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "tertiary"],
+            ["oneway", "yes"],
+            ["lanes", "3"],
+            ["turn:lanes", "left|through|through;right"],
+        ])
+        .unwrap();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::default())
+            .unwrap()
+            .road;
+        let roundtrip = lanes_to_tags(&road, &locale, &LanesToTagsConfig::new(true)).unwrap();
+        assert_eq!(
+            roundtrip.get("turn:lanes"),
+            Some("left|through|through;right")
+        );
+    }
+}
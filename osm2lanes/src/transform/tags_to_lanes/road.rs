@@ -4,17 +4,38 @@ use std::iter;
 use super::infer::Infer;
 use super::oneway::Oneway;
 use super::separator::{
-    lane_pair_to_semantic_separator, lane_to_inner_edge_separator, lane_to_outer_edge_separator,
-    semantic_separator_to_lane,
+    centre_separator, lane_pair_to_semantic_separator, lane_to_inner_edge_separator,
+    lane_to_outer_edge_separator, semantic_separator_to_lane, LaneChange,
 };
 use super::TagsToLanesMsg;
 use crate::locale::{DrivingSide, Locale};
 use crate::metric::{Metre, Speed};
-use crate::road::{Access as LaneAccess, Designated, Direction, Lane};
-use crate::tag::{Access as AccessValue, Highway, TagKey, Tags, HIGHWAY, LIFECYCLE};
+use crate::road::{
+    Access as LaneAccess, Color, Cycleway, Designated, Direction, Footway, Lane,
+    ParkingOrientation, Road, Steps, Style, TurnDirection,
+};
+use crate::tag::{
+    Access as AccessValue, Highway, HighwayType, TagKey, TrackedTags, HIGHWAY, LIFECYCLE,
+};
 use crate::transform::error::{RoadError, RoadWarnings};
 use crate::transform::tags_to_lanes::counts::{CentreTurnLaneScheme, Counts};
-use crate::transform::tags_to_lanes::modes::BusLaneCount;
+use crate::transform::tags_to_lanes::modes::{self, BusLaneCount};
+
+/// Keys read by [`RoadBuilder::from`]
+pub(super) const KEYS: &[&str] = &[
+    "highway",
+    "construction",
+    "proposed",
+    "access",
+    "bus",
+    "psv",
+    "vehicle",
+    "motor_vehicle",
+    "motor_vehicle:conditional",
+    "maxspeed:advisory",
+    "width",
+    "est_width",
+];
 
 #[derive(Debug)]
 pub(in crate::transform) struct LaneBuilderError(pub &'static str);
@@ -54,6 +75,8 @@ pub struct Access {
     pub taxi: Infer<AccessValue>,
     pub bus: Infer<AccessValue>,
     pub motor: Infer<AccessValue>,
+    pub hgv: Infer<AccessValue>,
+    pub goods: Infer<AccessValue>,
 }
 
 impl From<Access> for Option<LaneAccess> {
@@ -63,6 +86,8 @@ impl From<Access> for Option<LaneAccess> {
             && inferred.taxi.is_none()
             && inferred.bus.is_none()
             && inferred.motor.is_none()
+            && inferred.hgv.is_none()
+            && inferred.goods.is_none()
         {
             return None;
         }
@@ -72,6 +97,8 @@ impl From<Access> for Option<LaneAccess> {
             taxi: inferred.taxi.some(),
             bus: inferred.bus.some(),
             motor: inferred.motor.some(),
+            hgv: inferred.hgv.some(),
+            goods: inferred.goods.some(),
         })
     }
 }
@@ -84,7 +111,25 @@ pub struct LaneBuilder {
     pub designated: Infer<Designated>,
     pub width: Width,
     pub max_speed: Infer<Speed>,
+    pub advisory_speed: Infer<Speed>,
     pub access: Access,
+    pub conditional: Infer<String>,
+    pub orientation: Infer<ParkingOrientation>,
+    pub parking_condition: Infer<String>,
+    pub fee: Infer<bool>,
+    pub steps: Infer<Steps>,
+    pub cycleway: Infer<Cycleway>,
+    pub footway: Infer<Footway>,
+    pub turn: Infer<Vec<TurnDirection>>,
+    pub surface: Infer<String>,
+    /// Width of a painted buffer between this lane and the lane inside of it
+    pub buffer: Infer<Metre>,
+    /// Physical separation in place of the painted buffer, e.g. a kerb
+    pub buffer_style: Infer<Style>,
+    /// Colour of the paint of the buffer, e.g. green for a cycle lane
+    pub buffer_color: Infer<Color>,
+    /// Whether other traffic may enter this lane, e.g. an advisory cycle lane
+    pub lane_change: Infer<LaneChange>,
 }
 
 impl LaneBuilder {
@@ -112,15 +157,29 @@ impl LaneBuilder {
                     designated: self.designated.some().unwrap(),
                     width,
                     max_speed: self.max_speed.some(),
+                    advisory_speed: self.advisory_speed.some(),
                     access: self.access.into(),
+                    conditional: self.conditional.some(),
+                    steps: self.steps.some(),
+                    cycleway: self.cycleway.some(),
+                    footway: self.footway.some(),
+                    turn: self.turn.some(),
                 }
             },
             Some(LaneType::Parking) => Lane::Parking {
                 direction: self.direction.some().unwrap(),
                 designated: self.designated.some().unwrap(),
                 width,
+                orientation: self.orientation.some(),
+                conditional: self.conditional.some(),
+                condition: self.parking_condition.some(),
+                fee: self.fee.some(),
+            },
+            Some(LaneType::Shoulder) => Lane::Shoulder {
+                width,
+                surface: self.surface.some(),
+                access: self.access.into(),
             },
-            Some(LaneType::Shoulder) => Lane::Shoulder { width },
             None => panic!(),
         }
     }
@@ -132,6 +191,79 @@ impl LaneBuilder {
         // check back after v1.0.0 to see if this is still the case
         self
     }
+
+    /// The builder of an already built lane, or `None` for a separator
+    fn from_lane(lane: &Lane) -> Option<Self> {
+        match lane {
+            Lane::Travel {
+                direction,
+                designated,
+                width,
+                max_speed,
+                advisory_speed,
+                access,
+                conditional,
+                steps,
+                cycleway,
+                footway,
+                turn,
+            } => Some(Self {
+                r#type: Infer::Direct(LaneType::Travel),
+                direction: Infer::direct(*direction),
+                designated: Infer::Direct(*designated),
+                width: Width {
+                    target: Infer::direct(*width),
+                    ..Default::default()
+                },
+                max_speed: Infer::direct(*max_speed),
+                advisory_speed: Infer::direct(*advisory_speed),
+                access: access.clone().into(),
+                conditional: Infer::direct(conditional.clone()),
+                steps: Infer::direct(*steps),
+                cycleway: Infer::direct(*cycleway),
+                footway: Infer::direct(*footway),
+                turn: Infer::direct(turn.clone()),
+                ..Default::default()
+            }),
+            Lane::Parking {
+                direction,
+                designated,
+                width,
+                orientation,
+                conditional,
+                condition,
+                fee,
+            } => Some(Self {
+                r#type: Infer::Direct(LaneType::Parking),
+                direction: Infer::Direct(*direction),
+                designated: Infer::Direct(*designated),
+                width: Width {
+                    target: Infer::direct(*width),
+                    ..Default::default()
+                },
+                orientation: Infer::direct(*orientation),
+                conditional: Infer::direct(conditional.clone()),
+                parking_condition: Infer::direct(condition.clone()),
+                fee: Infer::direct(*fee),
+                ..Default::default()
+            }),
+            Lane::Shoulder {
+                width,
+                surface,
+                access,
+            } => Some(Self {
+                r#type: Infer::Direct(LaneType::Shoulder),
+                width: Width {
+                    target: Infer::direct(*width),
+                    ..Default::default()
+                },
+                surface: Infer::direct(surface.clone()),
+                access: access.clone().into(),
+                ..Default::default()
+            }),
+            Lane::Separator { .. } => None,
+        }
+    }
 }
 
 pub(in crate::transform) struct RoadBuilder {
@@ -139,31 +271,101 @@ pub(in crate::transform) struct RoadBuilder {
     backward_lanes: VecDeque<LaneBuilder>,
     pub highway: Highway,
     pub oneway: Oneway,
+    /// Total width of the road, see [`road_width`]
+    pub width: Infer<Metre>,
+}
+
+/// Modes that can be the only ones allowed on a road with `access=no`
+pub(in crate::transform::tags_to_lanes) const ACCESS_ONLY: [(&str, Designated); 3] = [
+    ("bus", Designated::Bus),
+    ("psv", Designated::Bus),
+    ("bicycle", Designated::Bicycle),
+];
+
+/// The mode the lanes are designated for when `access=no`
+/// but a single mode is `yes` or `designated`, e.g. `access=no` + `bus=yes`
+pub(in crate::transform::tags_to_lanes) fn access_only(tags: &TrackedTags) -> Option<Designated> {
+    if !tags.is("access", "no") {
+        return None;
+    }
+    let mut modes = ACCESS_ONLY
+        .iter()
+        .filter(|(key, _)| tags.is_any(*key, &["yes", "designated"]))
+        .map(|(_, designated)| *designated);
+    let first = modes.next()?;
+    modes.all(|designated| designated == first).then(|| first)
+}
+
+impl From<Option<LaneAccess>> for Access {
+    fn from(access: Option<LaneAccess>) -> Self {
+        access.map_or_else(Self::default, |access| Self {
+            foot: Infer::direct(access.foot),
+            bicycle: Infer::direct(access.bicycle),
+            taxi: Infer::direct(access.taxi),
+            bus: Infer::direct(access.bus),
+            motor: Infer::direct(access.motor),
+            hgv: Infer::direct(access.hgv),
+            goods: Infer::direct(access.goods),
+        })
+    }
+}
+
+/// How far `width=*` and `est_width=*` may differ before they are considered to disagree
+const WIDTH_ESTIMATE_TOLERANCE: Metre = Metre::new(1.0);
+
+/// The width of the road from `width=*`, or else the estimate from `est_width=*`.
+/// The exact `width=*` wins when both are present, with a warning if they disagree.
+fn road_width(tags: &TrackedTags, warnings: &mut RoadWarnings) -> Infer<Metre> {
+    let width = tags.get_parsed::<_, f64>("width", warnings);
+    let est_width = tags.get_parsed::<_, f64>("est_width", warnings);
+    match (width, est_width) {
+        (Some(width), Some(est_width)) => {
+            if (width - est_width).abs() > WIDTH_ESTIMATE_TOLERANCE.val() {
+                warnings.push(TagsToLanesMsg::ambiguous_tags(
+                    tags.subset(&["width", "est_width"]),
+                ));
+            }
+            Infer::Direct(Metre::new(width))
+        },
+        (Some(width), None) => Infer::Direct(Metre::new(width)),
+        (None, Some(est_width)) => Infer::Default(Metre::new(est_width)),
+        (None, None) => Infer::None,
+    }
 }
 
 impl RoadBuilder {
     #[allow(clippy::items_after_statements, clippy::too_many_lines)]
     pub fn from(
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         warnings: &mut RoadWarnings,
     ) -> Result<Self, RoadError> {
         let highway = Highway::from_tags(tags);
         let highway = match highway {
-            Err(None) => return Err(TagsToLanesMsg::unsupported_str("way is not highway").into()),
-            Err(Some(s)) => return Err(TagsToLanesMsg::unsupported_tag(HIGHWAY, &s).into()),
+            Err(None) => {
+                warnings.push(TagsToLanesMsg::unsupported_str("way is not highway"));
+                Highway::active(HighwayType::UnknownRoad)
+            },
+            Err(Some(s)) => {
+                warnings.push(TagsToLanesMsg::unsupported_tag(HIGHWAY, &s));
+                Highway::active(HighwayType::UnknownRoad)
+            },
             Ok(highway) => match highway {
                 highway if highway.is_supported() => highway,
                 _ => {
-                    return Err(TagsToLanesMsg::unimplemented_tags(tags.subset(&LIFECYCLE)).into());
+                    return Err(TagsToLanesMsg::unimplemented(
+                        "unsupported highway type",
+                        tags.subset(&LIFECYCLE),
+                    )
+                    .into());
                 },
             },
         };
 
-        let oneway = Oneway::from_tags(tags, locale, warnings)?;
+        let oneway = Oneway::from_tags(tags, &highway, locale, warnings)?;
+        let road_width = road_width(tags, warnings);
 
-        let designated = if tags.is("access", "no")
-            && (tags.is("bus", "yes") || tags.is("psv", "yes")) // West Seattle
+        let designated = if highway.r#type() == HighwayType::Busway
             || tags
                 .get("motor_vehicle:conditional")
                 .map_or(false, |x| x.starts_with("no"))
@@ -171,18 +373,15 @@ impl RoadBuilder {
         // Example: 3rd Ave in downtown Seattle
         {
             Designated::Bus
+        } else if let Some(designated) = access_only(tags) {
+            // Example: West Seattle
+            designated
         } else {
             Designated::Motor
         };
 
         const MAXSPEED: TagKey = TagKey::from("maxspeed");
-        let max_speed = match tags.get(MAXSPEED).map(str::parse::<Speed>).transpose() {
-            Ok(max_speed) => max_speed,
-            Err(_e) => {
-                warnings.push(TagsToLanesMsg::unsupported_tags(tags.subset(&[MAXSPEED])));
-                None
-            },
-        };
+        let advisory_speed = modes::speed(tags, MAXSPEED + "advisory", locale, warnings);
 
         let width = locale.travel_width(&designated, highway.r#type());
         let width = Width {
@@ -204,7 +403,7 @@ impl RoadBuilder {
         );
         log::trace!("lane counts: {lane_counts:?}");
 
-        let road = if let Counts::Directional {
+        let mut road = if let Counts::Directional {
             forward,
             backward,
             centre_turn_lane,
@@ -216,7 +415,7 @@ impl RoadBuilder {
                 r#type: Infer::Default(LaneType::Travel),
                 direction: Infer::Default(Direction::Forward),
                 designated: Infer::Default(designated),
-                max_speed: Infer::direct(max_speed),
+                advisory_speed: Infer::direct(advisory_speed),
                 width: width.clone(),
                 ..Default::default()
             })
@@ -226,7 +425,7 @@ impl RoadBuilder {
                 r#type: Infer::Default(LaneType::Travel),
                 direction: Infer::Default(Direction::Backward),
                 designated: Infer::Default(designated),
-                max_speed: Infer::direct(max_speed),
+                advisory_speed: Infer::direct(advisory_speed),
                 width: width.clone(),
                 ..Default::default()
             })
@@ -248,6 +447,7 @@ impl RoadBuilder {
                 backward_lanes,
                 highway,
                 oneway,
+                width: road_width,
             }
         } else {
             RoadBuilder {
@@ -261,12 +461,93 @@ impl RoadBuilder {
                 backward_lanes: VecDeque::new(),
                 highway,
                 oneway,
+                width: road_width,
             }
         };
 
+        // Access on a road, rather than a path, is for travelling on the carriageway.
+        // The key of each mode wins over `vehicle=*` and then `access=*`,
+        // which is already the designation of the lanes when it leaves a single mode.
+        // So `access=yes` opens a motorway to the modes without a more specific tag.
+        if !road.highway.is_supported_non_motorized() {
+            let access = (designated == Designated::Motor)
+                .then(|| tags.get_parsed::<_, AccessValue>("access", warnings))
+                .flatten();
+            let vehicle = tags
+                .get_parsed::<_, AccessValue>("vehicle", warnings)
+                .or_else(|| access.clone());
+            let mut mode = |key: &str, fallback: Option<&AccessValue>| {
+                tags.get_parsed::<_, AccessValue>(key, warnings)
+                    .or_else(|| fallback.cloned())
+            };
+            let foot = mode("foot", access.as_ref());
+            let bicycle = mode("bicycle", vehicle.as_ref());
+            let motor = mode("motor_vehicle", vehicle.as_ref());
+            for lane in road.lanes_ltr_mut(locale) {
+                if let Some(foot) = &foot {
+                    lane.access.foot = Infer::Direct(foot.clone());
+                }
+                if let Some(bicycle) = &bicycle {
+                    lane.access.bicycle = Infer::Direct(bicycle.clone());
+                }
+                if let Some(motor) = &motor {
+                    lane.access.motor = Infer::Direct(motor.clone());
+                }
+                if let Some(access) = &access {
+                    lane.access.bus = Infer::Direct(access.clone());
+                }
+            }
+        }
+
         Ok(road)
     }
 
+    /// Split the lanes of a road, ignoring its separators, into forward and backward lanes.
+    /// The forward lanes start at the first motor or bus lane not going backward,
+    /// or after the last motor or bus lane if they all do.
+    pub fn from_road(road: &Road, locale: &Locale) -> Self {
+        let mut lanes: Vec<LaneBuilder> = road
+            .lanes
+            .iter()
+            .filter_map(LaneBuilder::from_lane)
+            .collect();
+        // Backward lanes are on the left of the forward lanes when driving on the right
+        if locale.driving_side == DrivingSide::Left {
+            lanes.reverse();
+        }
+        let is_carriageway = |lane: &LaneBuilder| {
+            matches!(lane.r#type.some(), Some(LaneType::Travel))
+                && matches!(
+                    lane.designated.some(),
+                    Some(Designated::Motor | Designated::Bus)
+                )
+        };
+        let split = lanes
+            .iter()
+            .position(|lane| {
+                is_carriageway(lane) && lane.direction.some() != Some(Direction::Backward)
+            })
+            .or_else(|| {
+                lanes
+                    .iter()
+                    .rposition(is_carriageway)
+                    .and_then(|index| index.checked_add(1))
+            })
+            .unwrap_or(0);
+        let forward_lanes: VecDeque<LaneBuilder> = lanes.split_off(split).into();
+        let backward_lanes: VecDeque<LaneBuilder> = lanes.into_iter().rev().collect();
+        let oneway = Oneway::from(
+            !backward_lanes.iter().any(is_carriageway) && forward_lanes.iter().any(is_carriageway),
+        );
+        Self {
+            forward_lanes,
+            backward_lanes,
+            highway: road.highway.clone(),
+            oneway,
+            width: Infer::None,
+        }
+    }
+
     /// Number of lanes
     ///
     /// # Panics
@@ -319,7 +600,7 @@ impl RoadBuilder {
         self.backward_lanes.back_mut()
     }
     /// Push new inner-most forward lane
-    pub fn _push_forward_inside(&mut self, lane: LaneBuilder) {
+    pub fn push_forward_inside(&mut self, lane: LaneBuilder) {
         self.forward_lanes.push_front(lane);
     }
     /// Push new outer-most forward lane
@@ -334,6 +615,24 @@ impl RoadBuilder {
     pub fn push_backward_outside(&mut self, lane: LaneBuilder) {
         self.backward_lanes.push_back(lane);
     }
+    /// Move lanes between the forward and backward lanes after their directions have changed.
+    /// As in [`RoadBuilder::from_road`], the forward lanes start at the first lane not going backward.
+    pub fn split_by_direction(&mut self) {
+        let backward_lanes = std::mem::take(&mut self.backward_lanes);
+        let forward_lanes = std::mem::take(&mut self.forward_lanes);
+        // Outer-most backward lane to outer-most forward lane
+        let mut lanes: Vec<LaneBuilder> = backward_lanes
+            .into_iter()
+            .rev()
+            .chain(forward_lanes)
+            .collect();
+        let split = lanes
+            .iter()
+            .position(|lane| lane.direction.some() != Some(Direction::Backward))
+            .unwrap_or(lanes.len());
+        self.forward_lanes = lanes.split_off(split).into();
+        self.backward_lanes = lanes.into_iter().rev().collect();
+    }
     /// Get lanes left to right
     pub fn lanes_ltr<'a>(&'a self, locale: &Locale) -> Box<dyn Iterator<Item = &LaneBuilder> + 'a> {
         match locale.driving_side {
@@ -421,7 +720,7 @@ impl RoadBuilder {
     )]
     pub fn into_ltr(
         mut self,
-        tags: &Tags,
+        tags: &TrackedTags,
         locale: &Locale,
         include_separators: bool,
         warnings: &mut RoadWarnings,
@@ -434,25 +733,11 @@ impl RoadBuilder {
                 .backward_outside()
                 .and_then(lane_to_outer_edge_separator);
             let middle_separator = match [self.forward_inside(), self.backward_inside()] {
-                [Some(forward), Some(backward)] => lane_pair_to_semantic_separator(
-                    [forward, backward],
-                    &self,
-                    tags,
-                    locale,
-                    warnings,
-                )
-                .and_then(|separator| {
-                    semantic_separator_to_lane(
-                        [forward, backward],
-                        &separator,
-                        &self,
-                        tags,
-                        locale,
-                        warnings,
-                    )
-                }),
+                [Some(forward), Some(backward)] => {
+                    centre_separator([forward, backward], &self, tags, locale, warnings)
+                },
                 [Some(lane), None] | [None, Some(lane)] => {
-                    lane_to_inner_edge_separator(lane.mirror()).map(Lane::mirror)
+                    lane_to_inner_edge_separator(lane.mirror(), locale).map(Lane::mirror)
                 },
                 [None, None] => return Err(RoadError::Msg(TagsToLanesMsg::internal("no lanes"))),
             };
@@ -3,16 +3,25 @@ use celes::Country;
 use crate::locale::Locale;
 use crate::metric::Metre;
 use crate::road::{Color, Designated, Direction, Lane, Marking, Markings, Style};
-use crate::tag::Tags;
+use crate::tag::{TagKey, TrackedTags};
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
 
 mod semantic;
 
 use semantic::{Overtake, Separator, SpeedClass};
 
-use self::semantic::LaneChange;
+pub(in crate::transform::tags_to_lanes) use self::semantic::LaneChange;
 use super::{LaneBuilder, LaneType, RoadBuilder};
 
+/// Keys read when inferring the separators between lanes
+pub(super) const KEYS: &[&str] = &["motorroad", "divider", "divider:width", "lane_markings"];
+
+const DIVIDER: TagKey = TagKey::from("divider");
+const LANE_MARKINGS: TagKey = TagKey::from("lane_markings");
+
+const MEDIAN_GRASS_WIDTH: Metre = Metre::new(2.0);
+const MEDIAN_KERB_WIDTH: Metre = Metre::new(1.0);
+
 #[derive(Clone, Copy)]
 enum DirectionChange {
     // One of the sides is bidirectional
@@ -27,7 +36,7 @@ enum DirectionChange {
 pub(in crate::transform::tags_to_lanes) fn lane_pair_to_semantic_separator(
     lanes: [&LaneBuilder; 2],
     road: &RoadBuilder,
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     warnings: &mut RoadWarnings,
 ) -> Option<Separator> {
@@ -54,6 +63,12 @@ pub(in crate::transform::tags_to_lanes) fn lane_pair_to_semantic_separator(
         ([_, (Some(LaneType::Shoulder), _)], _) => Some(Separator::Shoulder {
             speed: inside.max_speed.map(SpeedClass::from),
         }),
+        // Buffer
+        _ if outside.buffer.some().is_some() => Some(Separator::Buffer {
+            width: outside.buffer.some().unwrap(),
+            style: outside.buffer_style.some().unwrap_or(Style::NoFill),
+            color: outside.buffer_color.some(),
+        }),
         // Motor to motor
         ([(_, Some(Designated::Motor)), (_, Some(Designated::Motor))], _) => {
             motor_lane_pair_to_semantic_separator(
@@ -65,13 +80,28 @@ pub(in crate::transform::tags_to_lanes) fn lane_pair_to_semantic_separator(
                 warnings,
             )
         },
+        // Bus to bus, e.g. highway=busway
+        ([(_, Some(Designated::Bus)), (_, Some(Designated::Bus))], _) => {
+            motor_lane_pair_to_semantic_separator(
+                [inside, outside],
+                direction_change,
+                road,
+                tags,
+                locale,
+                warnings,
+            )
+        },
         // Modal separation
         ([(_, Some(inside_designated)), (_, Some(outside_designated))], _)
             if inside_designated != outside_designated =>
         {
             Some(Separator::Modal {
                 speed: inside.max_speed.map(SpeedClass::from),
-                change: LaneChange::default(),
+                change: if outside.lane_change.is_none() {
+                    inside.lane_change
+                } else {
+                    outside.lane_change
+                },
                 inside: inside_designated,
                 outside: outside_designated,
             })
@@ -87,26 +117,75 @@ pub(in crate::transform::tags_to_lanes) fn lane_pair_to_semantic_separator(
     }
 }
 
+/// Raised median from `divider=*`
+/// <https://wiki.openstreetmap.org/wiki/Key:divider>
+fn divider_to_semantic_separator(
+    tags: &TrackedTags,
+    warnings: &mut RoadWarnings,
+) -> Option<Separator> {
+    let (grass, default_width) = match tags.get(DIVIDER)? {
+        "grass" => (true, MEDIAN_GRASS_WIDTH),
+        "curb" | "kerb" => (false, MEDIAN_KERB_WIDTH),
+        _ => return None,
+    };
+    let width = tags
+        .get_parsed::<_, f64>(DIVIDER + "width", warnings)
+        .map_or(default_width, Metre::new);
+    Some(Separator::Median { width, grass })
+}
+
+/// Given the inside forward and inside backward lanes
+/// what should the separator at the centre of the road be
+pub(in crate::transform::tags_to_lanes) fn centre_separator(
+    lanes: [&LaneBuilder; 2],
+    road: &RoadBuilder,
+    tags: &TrackedTags,
+    locale: &Locale,
+    warnings: &mut RoadWarnings,
+) -> Option<Lane> {
+    let separator = divider_to_semantic_separator(tags, warnings)
+        .or_else(|| lane_pair_to_semantic_separator(lanes, road, tags, locale, warnings))?;
+    semantic_separator_to_lane(lanes, &separator, road, tags, locale, warnings)
+}
+
 #[allow(clippy::unnecessary_wraps)]
 fn motor_lane_pair_to_semantic_separator(
     [inside, _outside]: [&LaneBuilder; 2],
     direction_change: DirectionChange,
     road: &RoadBuilder,
-    _tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     _warnings: &mut RoadWarnings,
 ) -> Option<Separator> {
-    match road
-        .lanes_ltr(locale)
-        .filter(|lane| {
-            matches!(lane.r#type.some(), Some(LaneType::Travel))
-                && matches!(
-                    lane.designated.some(),
-                    Some(Designated::Motor | Designated::Bus),
-                )
-        })
-        .count()
-    {
+    // No painted lines, the carriageway is shared
+    // https://wiki.openstreetmap.org/wiki/Key:lane_markings
+    if tags.is(LANE_MARKINGS, "no") {
+        return None;
+    }
+    let is_carriageway = |lane: &&LaneBuilder| {
+        matches!(lane.r#type.some(), Some(LaneType::Travel))
+            && matches!(
+                lane.designated.some(),
+                Some(Designated::Motor | Designated::Bus),
+            )
+    };
+    let carriageway = road.lanes_ltr(locale).filter(is_carriageway).count();
+    // Narrow streets have no centre line,
+    // the carriageway being what is left of the road width beside the other lanes
+    if carriageway == 2 {
+        if let Some(width) = road.width.some() {
+            let beside: f64 = road
+                .lanes_ltr(locale)
+                .filter(|lane| !is_carriageway(lane))
+                .filter_map(|lane| lane.width.target.some())
+                .map(|width| width.val())
+                .sum();
+            if width.val() - beside < locale.centre_line_min_width().val() {
+                return None;
+            }
+        }
+    }
+    match carriageway {
         2 => Some(Separator::Centre {
             speed: inside.max_speed.map(SpeedClass::from),
             overtake: Overtake::default(),
@@ -133,7 +212,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
     [inside, outside]: [&LaneBuilder; 2],
     separator: &Separator,
     _road: &RoadBuilder,
-    tags: &Tags,
+    tags: &TrackedTags,
     locale: &Locale,
     warnings: &mut RoadWarnings,
 ) -> Option<Lane> {
@@ -143,7 +222,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
             markings: Markings::new(vec![Marking {
                 style: Style::KerbUp,
                 color: None,
-                width: Some(Marking::DEFAULT_WIDTH),
+                width: Some(locale.separator_widths.kerb),
             }]),
         }),
         // Shoulder
@@ -157,7 +236,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
                             markings: Markings::new(vec![Marking {
                                 style: Style::SolidLine,
                                 color: Some(Color::White),
-                                width: Some(Marking::DEFAULT_WIDTH),
+                                width: Some(locale.separator_widths.line),
                             }]),
                         });
                     }
@@ -167,7 +246,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
                 markings: Markings::new(vec![Marking {
                     style: Style::SolidLine,
                     color: Some(Color::White),
-                    width: Some(Marking::DEFAULT_WIDTH),
+                    width: Some(locale.separator_widths.line),
                 }]),
             })
         },
@@ -189,7 +268,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
                                 Marking {
                                     style: Style::SolidLine,
                                     color: Some(Color::Green),
-                                    width: Some(2.0_f64 * Marking::DEFAULT_SPACE),
+                                    width: Some(2.0_f64 * locale.separator_widths.space),
                                 },
                                 Marking {
                                     style: Style::BrokenLine,
@@ -211,17 +290,17 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
                         Marking {
                             style: Style::SolidLine,
                             color: Some(Color::White),
-                            width: Some(Marking::DEFAULT_WIDTH),
+                            width: Some(locale.separator_widths.line),
                         },
                         Marking {
                             style: Style::NoFill,
                             color: None,
-                            width: Some(Marking::DEFAULT_SPACE),
+                            width: Some(locale.separator_widths.space),
                         },
                         Marking {
                             style: Style::SolidLine,
                             color: Some(Color::White),
-                            width: Some(Marking::DEFAULT_WIDTH),
+                            width: Some(locale.separator_widths.line),
                         },
                     ])
                 } else {
@@ -237,20 +316,66 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
             markings: Markings::new(vec![Marking {
                 style: Style::DottedLine,
                 color: Some(Color::White),
-                width: Some(Marking::DEFAULT_WIDTH),
+                width: Some(locale.separator_widths.line),
+            }]),
+        }),
+        // Physical separation
+        Separator::Buffer { width, style, .. } if *style != Style::NoFill => {
+            Some(Lane::Separator {
+                markings: Markings::new(vec![Marking {
+                    style: *style,
+                    color: None,
+                    width: Some(*width),
+                }]),
+            })
+        },
+        // Painted area
+        Separator::Buffer {
+            width,
+            style,
+            color,
+        } => Some(Lane::Separator {
+            markings: Markings::new(vec![
+                Marking {
+                    style: Style::SolidLine,
+                    color: Some(Color::White),
+                    width: Some(locale.separator_widths.line),
+                },
+                Marking {
+                    style: *style,
+                    color: *color,
+                    width: Some(*width),
+                },
+                Marking {
+                    style: Style::SolidLine,
+                    color: Some(Color::White),
+                    width: Some(locale.separator_widths.line),
+                },
+            ]),
+        }),
+        Separator::Median { width, grass } => Some(Lane::Separator {
+            markings: Markings::new(vec![Marking {
+                style: Style::Median,
+                color: if *grass { Some(Color::Green) } else { None },
+                width: Some(*width),
             }]),
         }),
         // Modal separation
-        Separator::Modal { .. } => {
+        Separator::Modal { change, .. } => {
             warnings.push(TagsToLanesMsg::separator_locale_unused(
                 inside.clone(),
                 outside.clone(),
             ));
             Some(Lane::Separator {
                 markings: Markings::new(vec![Marking {
-                    style: Style::SolidLine,
+                    // Only a known permitted lane change is dashed, e.g. an advisory cycle lane
+                    style: if change.some() == Some(LaneChange::Permitted) {
+                        Style::DashedLine
+                    } else {
+                        Style::SolidLine
+                    },
                     color: Some(Color::White),
-                    width: Some(Marking::DEFAULT_WIDTH),
+                    width: Some(locale.separator_widths.line),
                 }]),
             })
         },
@@ -264,7 +389,7 @@ pub(in crate::transform::tags_to_lanes) fn semantic_separator_to_lane(
                 markings: Markings::new(vec![Marking {
                     style: Style::BrokenLine,
                     color: Some(Color::Red),
-                    width: Some(Marking::DEFAULT_WIDTH),
+                    width: Some(locale.separator_widths.line),
                 }]),
             })
         },
@@ -283,12 +408,177 @@ pub(super) fn lane_to_outer_edge_separator(_lane: &LaneBuilder) -> Option<Lane>
 /// what should the separator be.
 /// Lanes are defined inside to outside
 #[allow(clippy::unnecessary_wraps)]
-pub(super) fn lane_to_inner_edge_separator(_lane: &LaneBuilder) -> Option<Lane> {
+pub(super) fn lane_to_inner_edge_separator(_lane: &LaneBuilder, locale: &Locale) -> Option<Lane> {
     Some(Lane::Separator {
         markings: Markings::new(vec![Marking {
             style: Style::SolidLine,
             color: Some(Color::White),
-            width: Some(Marking::DEFAULT_WIDTH),
+            width: Some(locale.separator_widths.line),
         }]),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::{Color, Lane, Marking, Style};
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn divider_grass() {
+        let tags = Tags::from_str_pairs(&[
+            ["highway", "secondary"],
+            ["lanes", "2"],
+            ["divider", "grass"],
+        ])
+        .unwrap();
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+            .unwrap()
+            .road;
+        let medians: Vec<&Marking> = road
+            .lanes
+            .iter()
+            .filter_map(|lane| match lane {
+                Lane::Separator { markings } => Some(markings.iter()),
+                _ => None,
+            })
+            .flatten()
+            .filter(|marking| marking.style == Style::Median)
+            .collect();
+        assert_eq!(
+            medians,
+            vec![&Marking {
+                style: Style::Median,
+                width: Some(Metre::new(2.0)),
+                color: Some(Color::Green),
+            }]
+        );
+    }
+
+    #[test]
+    fn centre_line_by_width() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let separators = |width: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["width", width],
+            ])
+            .unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+                .unwrap()
+                .road
+                .lanes
+                .iter()
+                .filter(|lane| lane.is_separator())
+                .count()
+        };
+        assert_eq!(separators("7"), 1);
+        assert_eq!(separators("4"), 0);
+    }
+
+    #[test]
+    fn centre_line_beside_shoulders() {
+        let locale = Locale::builder()
+            .driving_side(DrivingSide::Right)
+            .iso_3166("NL")
+            .build();
+        let centre_line = |tags: &[[&str; 2]]| {
+            let tags = Tags::from_str_pairs(tags).unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+                .unwrap()
+                .road
+                .lanes
+                .windows(3)
+                .any(|lanes| match lanes {
+                    [left, Lane::Separator { .. }, right] => left.is_motor() && right.is_motor(),
+                    _ => false,
+                })
+        };
+        // 5.5m less two 0.6m shoulders is too narrow
+        assert!(!centre_line(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["width", "5.5"],
+            ["shoulder", "both"],
+        ]));
+        // Unknown width
+        assert!(centre_line(&[
+            ["highway", "residential"],
+            ["lanes", "2"],
+            ["shoulder", "both"],
+        ]));
+    }
+
+    #[test]
+    fn no_lane_markings() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let separators = |lane_markings: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["width", "7"],
+                ["lane_markings", lane_markings],
+            ])
+            .unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true))
+                .unwrap()
+                .road
+                .lanes
+                .iter()
+                .filter(|lane| lane.is_separator())
+                .count()
+        };
+        assert_eq!(separators("yes"), 1);
+        assert_eq!(separators("no"), 0);
+    }
+
+    #[test]
+    fn width_and_est_width() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road = |tags: &[[&str; 2]]| {
+            let tags = Tags::from_str_pairs(tags).unwrap();
+            let road_from_tags =
+                tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, true)).unwrap();
+            let separators = road_from_tags
+                .road
+                .lanes
+                .iter()
+                .filter(|lane| lane.is_separator())
+                .count();
+            (separators, road_from_tags.warnings.is_empty())
+        };
+        // The estimate is used without an exact width
+        assert_eq!(
+            road(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["est_width", "4"],
+            ]),
+            (0, true)
+        );
+        // The exact width wins, and agrees with the estimate
+        assert_eq!(
+            road(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["width", "7"],
+                ["est_width", "7.5"],
+            ]),
+            (1, true)
+        );
+        // The exact width wins, and conflicts with the estimate
+        assert_eq!(
+            road(&[
+                ["highway", "residential"],
+                ["lanes", "2"],
+                ["width", "7"],
+                ["est_width", "4"],
+            ]),
+            (1, false)
+        );
+    }
+}
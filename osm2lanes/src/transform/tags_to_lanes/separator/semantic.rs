@@ -1,5 +1,5 @@
 use crate::metric::{Metre, Speed};
-use crate::road::{Designated, Style};
+use crate::road::{Color, Designated, Style};
 use crate::transform::tags_to_lanes::Infer;
 
 /// Semantic speed class
@@ -50,10 +50,10 @@ impl Default for Overtake {
 ///
 /// Note: this does not take into account the local vehicle-specific rules,
 /// e.g. for motorcycle filtering or overtaking tractors.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum LaneChange {
     Permitted,
-    _Prohibited,
+    Prohibited,
 }
 
 impl Default for LaneChange {
@@ -87,15 +87,21 @@ pub enum Separator {
     // TODO: solve directionality
     Modal {
         speed: Infer<SpeedClass>,
-        change: LaneChange,
+        change: Infer<LaneChange>,
         inside: Designated,
         outside: Designated,
     },
-    /// Painted area
-    _Buffer { width: Metre, style: Style },
+    /// Painted area, coloured where the paint is
+    Buffer {
+        width: Metre,
+        style: Style,
+        color: Option<Color>,
+    },
     /// Kerb step
     // TODO: solve directionality
     Kerb,
     /// Grassy verge
     _Verge { width: Metre },
+    /// Raised median between opposite directions, grassed or paved
+    Median { width: Metre, grass: bool },
 }
@@ -1,69 +1,97 @@
 use crate::locale::Locale;
-use crate::tag::{TagKey, Tags};
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::tags_to_lanes::road::{access_only, ACCESS_ONLY};
 use crate::transform::{RoadWarnings, TagsToLanesMsg};
 
+/// Keys read by [`unsupported`], the transport mode restrictions,
+/// see <https://wiki.openstreetmap.org/wiki/Key:access#Transport_mode_restrictions>
+pub(super) const KEYS: &[&str] = &[
+    "access",
+    "dog",
+    "ski",
+    "inline_skates",
+    "horse",
+    "vehicle",
+    "bicycle",
+    "electric_bicycle",
+    "carriage",
+    "hand_cart",
+    "quadracycle",
+    "trailer",
+    "caravan",
+    "motor_vehicle",
+    "motorcycle",
+    "moped",
+    "mofa",
+    "motorcar",
+    "motorhome",
+    "tourist_bus",
+    "coach",
+    "goods",
+    "hgv",
+    "hgv_articulated",
+    "bdouble",
+    "agricultural",
+    "golf_cart",
+    "atv",
+    "snowmobile",
+    "psv",
+    "bus",
+    "taxi",
+    "minibus",
+    "share_taxi",
+    "hov",
+    "car_sharing",
+    "emergency",
+    "hazmat",
+    "disabled",
+    "roadtrain",
+    "hgv_caravan",
+    "lhv",
+    "tank",
+];
+
 /// Unsupported
 /// Catch-all for unsupported or unimplemented but known constructs
 #[allow(clippy::unnecessary_wraps)]
 pub fn unsupported(
-    tags: &Tags,
+    tags: &TrackedTags,
     _locale: &Locale,
     warnings: &mut RoadWarnings,
 ) -> Result<(), TagsToLanesMsg> {
-    // https://wiki.openstreetmap.org/wiki/Key:access#Transport_mode_restrictions
-    const ACCESS_KEYS: [&str; 43] = [
-        "access",
-        "dog",
-        "ski",
-        "inline_skates",
-        "horse",
-        "vehicle",
-        "bicycle",
-        "electric_bicycle",
-        "carriage",
-        "hand_cart",
-        "quadracycle",
-        "trailer",
-        "caravan",
-        "motor_vehicle",
-        "motorcycle",
-        "moped",
-        "mofa",
-        "motorcar",
-        "motorhome",
-        "tourist_bus",
-        "coach",
-        "goods",
-        "hgv",
-        "hgv_articulated",
-        "bdouble",
-        "agricultural",
-        "golf_cart",
-        "atv",
-        "snowmobile",
-        "psv",
-        "bus",
-        "taxi",
-        "minibus",
-        "share_taxi",
-        "hov",
-        "car_sharing",
-        "emergency",
-        "hazmat",
-        "disabled",
-        "roadtrain",
-        "hgv_caravan",
-        "lhv",
-        "tank",
-    ];
-    if ACCESS_KEYS
+    // Implied by https://wiki.openstreetmap.org/wiki/Tag:highway%3Dbusway
+    let busway = |k: &str| {
+        tags.is("highway", "busway")
+            && matches!(k, "bus" | "psv")
+            && tags.is_any(k, &["yes", "designated"])
+    };
+    // Lanes are designated for the only allowed mode
+    let has_only = access_only(tags).is_some();
+    let only = |k: &str| {
+        has_only
+            && (k == "access"
+                || ACCESS_ONLY.iter().any(|(key, _)| *key == k)
+                    && tags.is_any(k, &["yes", "designated"]))
+    };
+    // Used to choose the lanes of https://wiki.openstreetmap.org/wiki/Tag:highway%3Dpath
+    let path = |k: &str| tags.is("highway", "path") && k == "bicycle";
+    // Opens the carriageway to every mode
+    let open = |k: &str| k == "access" && tags.is("access", "yes");
+    // Cyclists walk along a non-motorized way, which becomes a footway
+    let dismount = |k: &str| {
+        k == "bicycle"
+            && tags.is("bicycle", "dismount")
+            && tags.is_any("highway", &["cycleway", "footway", "path", "steps"])
+    };
+    let implied = |k: &str| busway(k) || only(k) || path(k) || open(k) || dismount(k);
+    if KEYS
         .iter()
-        .any(|k| tags.get(TagKey::from(k)).is_some())
+        .any(|k| tags.get(TagKey::from(k)).is_some() && !implied(k))
     {
         warnings.push(TagsToLanesMsg::unimplemented(
             "access",
             // TODO, TagTree should support subset
-            tags.subset(&ACCESS_KEYS),
+            tags.subset(KEYS),
         ));
     }
     Ok(())
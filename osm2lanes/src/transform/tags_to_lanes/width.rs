@@ -0,0 +1,170 @@
+//! Widths of the carriageway lanes from `width:lanes=*`,
+//! see <https://wiki.openstreetmap.org/wiki/Key:width#Lanes>,
+//! and how they add up against `width=*`.
+
+use crate::metric::Metre;
+use crate::road::Lane;
+use crate::tag::{TagKey, TrackedTags};
+use crate::transform::{RoadWarnings, TagsToLanesMsg};
+
+const WIDTH: TagKey = TagKey::from("width");
+const LANES: TagKey = TagKey::from("lanes");
+
+/// Keys read by [`lanes`]
+pub(super) const KEYS: &[&str] = &["width:lanes"];
+
+/// Narrowest plausible carriageway lane, see [`check_lane_count`]
+const MIN_LANE_WIDTH: Metre = Metre::new(2.5);
+
+/// Total width of the road from `width=*`
+fn total(tags: &TrackedTags) -> Option<f64> {
+    tags.get(WIDTH).and_then(|width| width.parse::<f64>().ok())
+}
+
+/// Apply `width:lanes=*` to the carriageway lanes, left to right,
+/// which are then no longer `defaulted_widths`
+pub(in crate::transform::tags_to_lanes) fn lanes(
+    tags: &TrackedTags,
+    lanes: &mut [Lane],
+    defaulted_widths: &mut [bool],
+    warnings: &mut RoadWarnings,
+) {
+    let key = WIDTH + "lanes";
+    let value = match tags.get(&key) {
+        Some(value) => value,
+        None => return,
+    };
+    let carriageway: Vec<(&mut Lane, &mut bool)> = lanes
+        .iter_mut()
+        .zip(defaulted_widths.iter_mut())
+        .filter(|(lane, _defaulted)| lane.is_motor() || lane.is_bus())
+        .collect();
+    if value.split('|').count() != carriageway.len() {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[key, LANES])));
+        return;
+    }
+    for ((lane, defaulted), lane_value) in carriageway.into_iter().zip(value.split('|')) {
+        if lane_value.is_empty() {
+            continue;
+        }
+        match (lane, lane_value.parse::<f64>()) {
+            (Lane::Travel { width, .. }, Ok(lane_value)) => {
+                *width = Some(Metre::new(lane_value));
+                *defaulted = false;
+            },
+            _ => warnings.push(TagsToLanesMsg::unsupported_tag(key.clone(), value)),
+        }
+    }
+}
+
+/// Warn when the explicit widths of the carriageway lanes add up to more than `width=*`.
+/// The explicit widths are kept, as the data cannot tell which of them is wrong.
+pub(in crate::transform::tags_to_lanes) fn check_total(
+    tags: &TrackedTags,
+    lanes: &[Lane],
+    warnings: &mut RoadWarnings,
+) {
+    let total = match total(tags) {
+        Some(total) => total,
+        None => return,
+    };
+    let explicit: f64 = lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || lane.is_bus())
+        .filter_map(|lane| match lane {
+            Lane::Travel {
+                width: Some(width), ..
+            } => Some(width.val()),
+            _ => None,
+        })
+        .sum();
+    if explicit > total {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(
+            tags.subset(&[WIDTH, WIDTH + "lanes"]),
+        ));
+    }
+}
+
+/// Warn when `width=*` cannot fit the carriageway lanes at their narrowest plausible width,
+/// suggesting that the lane count or the width is wrong.
+pub(in crate::transform::tags_to_lanes) fn check_lane_count(
+    tags: &TrackedTags,
+    lanes: &[Lane],
+    warnings: &mut RoadWarnings,
+) {
+    let total = match total(tags) {
+        Some(total) => total,
+        None => return,
+    };
+    let minimum: f64 = lanes
+        .iter()
+        .filter(|lane| lane.is_motor() || lane.is_bus())
+        .map(|_| MIN_LANE_WIDTH.val())
+        .sum();
+    if minimum > total {
+        warnings.push(TagsToLanesMsg::ambiguous_tags(tags.subset(&[WIDTH, LANES])));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{DrivingSide, Locale};
+    use crate::metric::Metre;
+    use crate::road::Lane;
+    use crate::tag::Tags;
+    use crate::transform::{tags_to_lanes, TagsToLanesConfig};
+
+    #[test]
+    fn width_lanes_wider_than_width() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = |width: &str| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["oneway", "yes"],
+                ["lanes", "2"],
+                ["width", width],
+                ["width:lanes", "3.5|4.0"],
+            ])
+            .unwrap();
+            tags_to_lanes(&tags, &locale, &TagsToLanesConfig::new(false, false)).unwrap()
+        };
+
+        let too_narrow = road_from_tags("6");
+        let widths: Vec<Option<Metre>> = too_narrow
+            .road
+            .lanes
+            .iter()
+            .filter(|lane| lane.is_motor())
+            .map(|lane| match lane {
+                Lane::Travel { width, .. } => *width,
+                _ => None,
+            })
+            .collect();
+        assert_eq!(widths, vec![Some(Metre::new(3.5)), Some(Metre::new(4.0))]);
+        assert!(!too_narrow.warnings.is_empty());
+
+        assert!(road_from_tags("8").warnings.is_empty());
+    }
+
+    #[test]
+    fn too_many_lanes_for_width() {
+        let locale = Locale::builder().driving_side(DrivingSide::Right).build();
+        let road_from_tags = |width: &str, check_lane_width: bool| {
+            let tags = Tags::from_str_pairs(&[
+                ["highway", "residential"],
+                ["lanes", "4"],
+                ["width", width],
+            ])
+            .unwrap();
+            let config = TagsToLanesConfig {
+                check_lane_width,
+                ..TagsToLanesConfig::new(false, false)
+            };
+            tags_to_lanes(&tags, &locale, &config).unwrap()
+        };
+
+        assert!(!road_from_tags("6", true).warnings.is_empty());
+        assert!(road_from_tags("6", false).warnings.is_empty());
+        assert!(road_from_tags("12", true).warnings.is_empty());
+    }
+}